@@ -0,0 +1,97 @@
+//!
+//! Batch verification (random linear combination)
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+use std::collections::HashMap;
+
+use super::number::Number;
+use super::{EcOp, Point};
+
+/// Bit length of the random coefficients
+const COEF_BITS: usize = 128;
+
+/// Linear point equation  c_1 * P_1 + .. + c_m * P_m = O  (0 <= c_j < n)
+pub type Equation<T> = Vec<(Point<T>, T)>;
+
+/// Verify a batch of equations with one multi-scalar multiplication
+/// - sum a_i * (equation i) = O,  a_0 = 1,  a_i random in [1, 2^128)
+/// - equal points (e.g. the generator and repeated keys) are merged into one term
+/// - if the batch fails, it is split in halves until the failing equations are found
+/// - None stands for a malformed signature and always fails
+/// - Err(indices of the failing equations)
+pub fn verify_equations<T: Number, E: EcOp<T>>(
+    ec: &E,
+    eqs: &[Option<Equation<T>>],
+) -> Result<(), Vec<usize>> {
+    let mut bad: Vec<usize> = Vec::new();
+    let mut valid: Vec<usize> = Vec::with_capacity(eqs.len());
+    for (i, eq) in eqs.iter().enumerate() {
+        match eq {
+            Some(_) => valid.push(i),
+            None => bad.push(i),
+        }
+    }
+    verify_split(ec, eqs, &valid, &mut bad);
+
+    if bad.is_empty() {
+        Ok(())
+    } else {
+        bad.sort_unstable();
+        Err(bad)
+    }
+}
+
+/// Check the equations idx together, halve on failure
+fn verify_split<T: Number, E: EcOp<T>>(
+    ec: &E,
+    eqs: &[Option<Equation<T>>],
+    idx: &[usize],
+    bad: &mut Vec<usize>,
+) {
+    if idx.is_empty() || verify_combined(ec, eqs, idx) {
+        return;
+    }
+    if idx.len() == 1 {
+        bad.push(idx[0]);
+        return;
+    }
+    let (left, right) = idx.split_at(idx.len() / 2);
+    verify_split(ec, eqs, left, bad);
+    verify_split(ec, eqs, right, bad);
+}
+
+/// sum a_i * (equation i) == O  for i in idx  (exact for a single equation)
+fn verify_combined<T: Number, E: EcOp<T>>(
+    ec: &E,
+    eqs: &[Option<Equation<T>>],
+    idx: &[usize],
+) -> bool {
+    let n = ec.get_order();
+    let bits = COEF_BITS.min(n.bit_len() - 1);
+    let max = T::from(2u32).pow(bits as u32);
+
+    // merged terms, keyed by the compressed encoding of the point
+    let mut keys: HashMap<Vec<u8>, usize> = HashMap::new();
+    let mut terms: Vec<(Point<T>, T)> = Vec::new();
+    for (j, &i) in idx.iter().enumerate() {
+        let a = if j == 0 {
+            T::one()
+        } else {
+            T::gen_rand(&T::one(), &max)
+        };
+        for (p, c) in eqs[i].iter().flatten() {
+            let k = *keys.entry(ec.get_encoded(p)).or_insert_with(|| {
+                terms.push((p.clone(), T::zero()));
+                terms.len() - 1
+            });
+            terms[k].1 = (a.mul_ref(c) + &terms[k].1) % &n;
+        }
+    }
+    let terms: Vec<(&Point<T>, &T)> = terms
+        .iter()
+        .filter(|(_, c)| !c.is_zero())
+        .map(|(p, c)| (p, c))
+        .collect();
+    ec.is_zero(&ec.mul_multi(&terms))
+}
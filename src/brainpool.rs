@@ -0,0 +1,72 @@
+//!
+//! Brainpool curves (RFC 5639): r1 <-> t1 isomorphism
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+use super::ec_param::{EcParam, ParamOp};
+use super::number::Number;
+use super::Point;
+
+///   Z of RFC 5639, 3.x:  t1 = r1 under (x, y) -> (x * Z^2, y * Z^3)
+static TWIST_Z: [(&str, &[u8]); 7] = [
+    ("brainpoolP160", b"24DBFF5DEC9B986BBFE5295A29BFBAE45E0F5D0B"),
+    (
+        "brainpoolP192",
+        b"1B6F5CC8DB4DC7AF19458A9CB80DC2295E5EB9C3732104CB",
+    ),
+    (
+        "brainpoolP224",
+        b"2DF271E14427A346910CF7A2E6CFA7B3F484E5C2CCE1C8B730E28B3F",
+    ),
+    (
+        "brainpoolP256",
+        b"3E2D4BD9597B58639AE7AA669CAB9837CF5CF20A2C852D10F655668DFC150EF0",
+    ),
+    (
+        "brainpoolP320",
+        b"15F75CAF668077F7E85B42EB01F0A81FF56ECD6191D55CB82B7D861458A18FEFC3E5AB7496F3C7B1",
+    ),
+    (
+        "brainpoolP384",
+        b"41DFE8DD399331F7166A66076734A89CD0D2BCDB7D068E44E1F378F41ECBAE97\
+          D2D63DBC87BCCDDCCC5DA39E8589291C",
+    ),
+    (
+        "brainpoolP512",
+        b"12EE58E6764838B69782136F0F2D3BA06E27695716054092E60A80BEDB212B64\
+          E585D90BCE13761F85C3F1D2A64E3BE8FEA2220F01EBA5EEB0F35DBD29D922AB",
+    ),
+];
+
+/// Map a point on brainpoolPxxxr1 (ecid) to the isomorphic point on brainpoolPxxxt1
+pub fn r1_to_t1<T: Number>(ecid: &str, p_p: &Point<T>) -> Option<Point<T>> {
+    let (z, p) = get_twist(ecid, "r1")?;
+    Some(map_point(p_p, &z, &p))
+}
+
+/// Map a point on brainpoolPxxxt1 (ecid) to the isomorphic point on brainpoolPxxxr1
+pub fn t1_to_r1<T: Number>(ecid: &str, p_p: &Point<T>) -> Option<Point<T>> {
+    let (z, p) = get_twist(ecid, "t1")?;
+    Some(map_point(p_p, &Number::mod_inv(&z, &p), &p))
+}
+
+/// Get (Z, p) for brainpoolPxxx{r1,t1}
+fn get_twist<T: Number>(ecid: &str, suffix: &str) -> Option<(T, T)> {
+    let name = ecid.strip_suffix(suffix)?;
+    let (_, zb) = TWIST_Z.iter().find(|(id, _)| *id == name)?;
+    let z = T::from_bytes_radix(zb, 16);
+    let p = EcParam::new(ecid).get_prime();
+    Some((z, p))
+}
+
+/// (x, y, z) -> (x * u^2, y * u^3, z)  (Affine and Jacobian)
+fn map_point<T: Number>(p_p: &Point<T>, u: &T, p: &T) -> Point<T> {
+    let u2 = u.mul_ref(u) % p;
+    let u3 = u2.mul_ref(u) % p;
+    Point {
+        axis: p_p.axis,
+        x: p_p.x.mul_ref(&u2) % p,
+        y: p_p.y.mul_ref(&u3) % p,
+        z: p_p.z.clone(),
+    }
+}
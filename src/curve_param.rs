@@ -0,0 +1,279 @@
+//!
+//! Custom Elliptic Curve Parameters and Domain Parameter Validation
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+use std::{error, fmt};
+
+use super::ec_param::{EcParam, ParamOp};
+use super::ecc_a::EcpA;
+use super::number::Number;
+use super::EcOp;
+
+/// MOV threshold (SEC 1, 3.1.1.2.1):  p^B != 1 mod n for 1 <= B < MOV_B
+const MOV_B: u32 = 100;
+/// Miller-Rabin rounds
+const MR_ROUNDS: usize = 40;
+
+/// Domain parameter errors
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParamError {
+    /// Parameter is not a hexadecimal string
+    InvalidHex,
+    /// p is not an odd prime > 3
+    FieldNotPrime,
+    /// a, b, Gx or Gy is not in [0, p)
+    CoefficientOutOfRange,
+    /// 4a^3 + 27b^2 = 0 mod p
+    SingularCurve,
+    /// G does not satisfy the curve equation
+    BaseNotOnCurve,
+    /// n is not prime
+    OrderNotPrime,
+    /// n * G != O
+    WrongBaseOrder,
+    /// h != floor((sqrt(p) + 1)^2 / n) or n <= 4 * sqrt(p)
+    InvalidCofactor,
+    /// p^B = 1 mod n for some B < 100 (MOV attack)
+    MovCondition,
+    /// n * h = p (anomalous curve,  #E = p)
+    AnomalousCurve,
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamError::InvalidHex => write!(f, "Invalid hex string"),
+            ParamError::FieldNotPrime => write!(f, "Field size p is not prime"),
+            ParamError::CoefficientOutOfRange => write!(f, "Parameter out of range"),
+            ParamError::SingularCurve => write!(f, "Singular curve"),
+            ParamError::BaseNotOnCurve => write!(f, "Base point not on curve"),
+            ParamError::OrderNotPrime => write!(f, "Order n is not prime"),
+            ParamError::WrongBaseOrder => write!(f, "n * G is not the point at infinity"),
+            ParamError::InvalidCofactor => write!(f, "Invalid cofactor"),
+            ParamError::MovCondition => write!(f, "Curve fails the MOV condition"),
+            ParamError::AnomalousCurve => write!(f, "Anomalous curve (n * h = p)"),
+        }
+    }
+}
+
+impl error::Error for ParamError {}
+
+///   Owned EC curve parameters
+///   y^2 = x^3 + ax + b mod p,  G = (gx, gy),  order n,  cofactor h
+#[derive(Clone, Debug, PartialEq)]
+pub struct CurveParam<T: Number> {
+    id: String,
+    p: T,
+    a: T,
+    b: T,
+    gx: T,
+    gy: T,
+    n: T,
+    h: T,
+}
+
+impl<T: Number> CurveParam<T> {
+    /// Curve parameters from numbers
+    pub fn new(id: &str, p: T, a: T, b: T, g: (T, T), n: T, h: T) -> CurveParam<T> {
+        CurveParam {
+            id: id.to_string(),
+            p,
+            a,
+            b,
+            gx: g.0,
+            gy: g.1,
+            n,
+            h,
+        }
+    }
+
+    /// Curve parameters from hex strings (g: "04" || X || Y, as in EcParam)
+    pub fn from_hex(
+        id: &str,
+        p: &str,
+        a: &str,
+        b: &str,
+        g: &str,
+        n: &str,
+        h: &str,
+    ) -> Result<CurveParam<T>, ParamError> {
+        let g = g.strip_prefix("04").ok_or(ParamError::InvalidHex)?;
+        if g.len() % 2 != 0 {
+            return Err(ParamError::InvalidHex);
+        }
+        let (gx, gy) = g.split_at(g.len() / 2);
+        Ok(CurveParam {
+            id: id.to_string(),
+            p: from_hex(p)?,
+            a: from_hex(a)?,
+            b: from_hex(b)?,
+            gx: from_hex(gx)?,
+            gy: from_hex(gy)?,
+            n: from_hex(n)?,
+            h: from_hex(h)?,
+        })
+    }
+
+    pub fn get_ecid(&self) -> &str {
+        &self.id
+    }
+
+    /// Domain parameter validation (SEC 1, 3.1.1.2.1)
+    pub fn validate(&self) -> Result<(), ParamError> {
+        let (p, a, b, n, h) = (&self.p, &self.a, &self.b, &self.n, &self.h);
+        let zero = T::zero();
+
+        // p: odd prime (> 3)
+        if p <= &T::from(3) || !is_probable_prime(p) {
+            return Err(ParamError::FieldNotPrime);
+        }
+        // a, b, gx, gy in [0, p - 1]
+        for v in [a, b, &self.gx, &self.gy] {
+            if v < &zero || v >= p {
+                return Err(ParamError::CoefficientOutOfRange);
+            }
+        }
+        // 4a^3 + 27b^2 != 0 mod p
+        let a3 = a.mul_ref(a) % p * a % p;
+        let b2 = b.mul_ref(b) % p;
+        if (T::from(4) * a3 + T::from(27) * b2) % p == zero {
+            return Err(ParamError::SingularCurve);
+        }
+        // gy^2 = gx^3 + a gx + b mod p
+        let (x, y) = (&self.gx, &self.gy);
+        let rhs = (x.mul_ref(x) % p * x + a.mul_ref(x) + b) % p;
+        if y.mul_ref(y) % p != rhs {
+            return Err(ParamError::BaseNotOnCurve);
+        }
+        // n: prime,  n * G = O
+        if n <= &T::one() || !is_probable_prime(n) {
+            return Err(ParamError::OrderNotPrime);
+        }
+        let ec = EcpA::from_param(self);
+        if !ec.is_zero(&ec.mul(&ec.get_gp(), n)) {
+            return Err(ParamError::WrongBaseOrder);
+        }
+        // n > 4 sqrt(p),  h = floor((sqrt(p) + 1)^2 / n) = floor((p + 1 + floor(2 sqrt(p))) / n)
+        let h_exp = (p.add_ref(&T::one()) + isqrt(&(T::from(4) * p))) / n;
+        if n.mul_ref(n) <= T::from(16) * p || h != &h_exp {
+            return Err(ParamError::InvalidCofactor);
+        }
+        // p^B != 1 mod n,  1 <= B < 100
+        let q = p.clone() % n;
+        let mut t = T::one();
+        for _ in 1..MOV_B {
+            t = t * &q % n;
+            if t == T::one() {
+                return Err(ParamError::MovCondition);
+            }
+        }
+        // n * h != p
+        if &n.mul_ref(h) == p {
+            return Err(ParamError::AnomalousCurve);
+        }
+        Ok(())
+    }
+}
+
+impl<T: Number> From<&EcParam<'_>> for CurveParam<T> {
+    fn from(ec: &EcParam<'_>) -> CurveParam<T> {
+        CurveParam {
+            id: ec.get_ecid().to_string(),
+            p: ec.get_prime(),
+            a: ec.get_a(),
+            b: ec.get_b(),
+            gx: ec.get_gx(),
+            gy: ec.get_gy(),
+            n: ec.get_order(),
+            h: ec.get_cofactor(),
+        }
+    }
+}
+
+impl<T: Number> ParamOp<T> for CurveParam<T> {
+    fn get_prime(&self) -> T {
+        self.p.clone()
+    }
+
+    fn get_a(&self) -> T {
+        self.a.clone()
+    }
+
+    fn get_b(&self) -> T {
+        self.b.clone()
+    }
+
+    fn get_order(&self) -> T {
+        self.n.clone()
+    }
+
+    fn get_cofactor(&self) -> T {
+        self.h.clone()
+    }
+
+    fn get_gx(&self) -> T {
+        self.gx.clone()
+    }
+
+    fn get_gy(&self) -> T {
+        self.gy.clone()
+    }
+}
+
+fn from_hex<T: Number>(s: &str) -> Result<T, ParamError> {
+    if s.is_empty() || !s.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ParamError::InvalidHex);
+    }
+    Ok(T::from_bytes_radix(s.as_bytes(), 16))
+}
+
+/// floor(sqrt(a)),  a >= 0  (Newton's method)
+fn isqrt<T: Number>(a: &T) -> T {
+    if a.is_zero() {
+        return T::zero();
+    }
+    // x_0 = 2^ceil(bitlen(a) / 2) > sqrt(a),  x decreases to floor(sqrt(a))
+    let mut x = T::from(2).pow(a.bit_len().div_ceil(2) as u32);
+    loop {
+        let y = (a.clone() / &x + &x) >> 1;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Miller-Rabin probabilistic primality test
+fn is_probable_prime<T: Number>(n: &T) -> bool {
+    for q in [2u32, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+        let q = T::from(q);
+        if n == &q {
+            return true;
+        }
+        if (n.clone() % &q).is_zero() {
+            return false;
+        }
+    }
+    // n - 1 = 2^s * d
+    let n1 = n.sub_ref(&T::one());
+    let s = (0..).find(|&i| n1.test_bit(i)).unwrap();
+    let d = n1.clone() >> s;
+
+    let two = T::from(2);
+    'witness: for _ in 0..MR_ROUNDS {
+        let w = T::gen_rand(&two, &n1);
+        let mut x = T::mod_pow(&w, &d, n);
+        if x == T::one() || x == n1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = x.mul_ref(&x) % n;
+            if x == n1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
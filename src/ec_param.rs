@@ -4,14 +4,42 @@
 //  Written by blanclux
 //  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
 
-use std::str;
+use std::{error, fmt, str};
 
-use super::number::Number;
+use super::number::{fits_field, Number};
+
+/// Curve id not found in the parameter table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownCurve(pub String);
+
+impl fmt::Display for UnknownCurve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown curve: {}", self.0)
+    }
+}
+
+impl error::Error for UnknownCurve {}
+
+/// Err if the field of p does not fit the number type (see number::fits_field)
+/// - e.g. secp521r1 with Fix256:  reported as an unsupported curve
+pub(crate) fn check_field<T: Number>(id: &str, p: &T) -> Result<(), UnknownCurve> {
+    if fits_field(p) {
+        Ok(())
+    } else {
+        Err(UnknownCurve(format!(
+            "{} ({}-bit field too large for the number type)",
+            id,
+            p.bit_len()
+        )))
+    }
+}
 
 ///   EC curve parameters
-///   id,  a  b,  p,  g (04||X||Y),  order,   h
+///   id,  oid,  a  b,  p,  g (04||X||Y),  order,   h
+#[derive(Clone, Copy)]
 pub struct EcParam<'a> {
     pub id: &'a str,
+    pub oid: &'a str,
     pub a: &'a [u8],
     pub b: &'a [u8],
     pub p: &'a [u8],
@@ -20,9 +48,50 @@ pub struct EcParam<'a> {
     pub h: &'a [u8],
 }
 
-static CURVE_PARAMS: [EcParam; 8] = [
+static CURVE_PARAMS: [EcParam; 29] = [
+    EcParam {
+        id: "secp112r1",
+        oid: "1.3.132.0.6",
+        a: b"DB7C2ABF62E35E668076BEAD2088",
+        b: b"659EF8BA043916EEDE8911702B22",
+        p: b"DB7C2ABF62E35E668076BEAD208B",
+        g: b"0409487239995A5EE76B55F9C2F098A89CE5AF8724C0A23E0E0FF77500",
+        n: b"DB7C2ABF62E35E7628DFAC6561C5",
+        h: b"01",
+    },
+    EcParam {
+        id: "secp112r2",
+        oid: "1.3.132.0.7",
+        a: b"6127C24C05F38A0AAAF65C0EF02C",
+        b: b"51DEF1815DB5ED74FCC34C85D709",
+        p: b"DB7C2ABF62E35E668076BEAD208B",
+        g: b"044BA30AB5E892B4E1649DD0928643ADCD46F5882E3747DEF36E956E97",
+        n: b"36DF0AAFD8B8D7597CA10520D04B",
+        h: b"04",
+    },
+    EcParam {
+        id: "secp128r1",
+        oid: "1.3.132.0.28",
+        a: b"FFFFFFFDFFFFFFFFFFFFFFFFFFFFFFFC",
+        b: b"E87579C11079F43DD824993C2CEE5ED3",
+        p: b"FFFFFFFDFFFFFFFFFFFFFFFFFFFFFFFF",
+        g: b"04161FF7528B899B2D0C28607CA52C5B86CF5AC8395BAFEB13C02DA292DDED7A83",
+        n: b"FFFFFFFE0000000075A30D1B9038A115",
+        h: b"01",
+    },
+    EcParam {
+        id: "secp128r2",
+        oid: "1.3.132.0.29",
+        a: b"D6031998D1B3BBFEBF59CC9BBFF9AEE1",
+        b: b"5EEEFCA380D02919DC2C6558BB6D8A5D",
+        p: b"FFFFFFFDFFFFFFFFFFFFFFFFFFFFFFFF",
+        g: b"047B6AA5D85E572983E6FB32A7CDEBC14027B6916A894D3AEE7106FE805FC34B44",
+        n: b"3FFFFFFF7FFFFFFFBE0024720613B5A3",
+        h: b"04",
+    },
     EcParam {
         id: "secp160k1",
+        oid: "1.3.132.0.9",
         a: b"0000000000000000000000000000000000000000",
         b: b"0000000000000000000000000000000000000007",
         p: b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFAC73",
@@ -32,126 +101,302 @@ static CURVE_PARAMS: [EcParam; 8] = [
     },
     EcParam {
         id: "secp160r1",
+        oid: "1.3.132.0.8",
         a: b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF7FFFFFFC",
         b: b"1C97BEFC54BD7A8B65ACF89F81D4D4ADC565FA45",
         p: b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF7FFFFFFF",
         g: b"044A96B5688EF573284664698968C38BB913CBFC8223A628553168947D59DCC912042351377AC5FB32",
         n: b"0100000000000000000001F4C8F927AED3CA752257",
-        h: b"01"
+        h: b"01",
+    },
+    EcParam {
+        id: "secp160r2",
+        oid: "1.3.132.0.30",
+        a: b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFAC70",
+        b: b"B4E134D3FB59EB8BAB57274904664D5AF50388BA",
+        p: b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFAC73",
+        g: b"0452DCB034293A117E1F4FF11B30F7199D3144CE6DFEAFFEF2E331F296E071FA0DF9982CFEA7D43F2E",
+        n: b"0100000000000000000000351EE786A818F3A1A16B",
+        h: b"01",
     },
     EcParam {
         id: "secp192k1",
+        oid: "1.3.132.0.31",
         a: b"000000000000000000000000000000000000000000000000",
         b: b"000000000000000000000000000000000000000000000003",
         p: b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFEE37",
         g: b"04DB4FF10EC057E9AE26B07D0280B7F4341DA5D1B1EAE06C7D9B2F2F6D9C5628A7844163D015BE86344082AA88D95E2F9D",
         n: b"FFFFFFFFFFFFFFFFFFFFFFFE26F2FC170F69466A74DEFD8D",
-        h: b"01"
+        h: b"01",
+    },
+    EcParam {
+        id: "secp192r1",
+        oid: "1.2.840.10045.3.1.1",
+        a: b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFFFFFFFFFC",
+        b: b"64210519E59C80E70FA7E9AB72243049FEB8DEECC146B9B1",
+        p: b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFFFFFFFFFF",
+        g: b"04188DA80EB03090F67CBF20EB43A18800F4FF0AFD82FF101207192B95FFC8DA78631011ED6B24CDD573F977A11E794811",
+        n: b"FFFFFFFFFFFFFFFFFFFFFFFF99DEF836146BC9B1B4D22831",
+        h: b"01",
     },
     EcParam {
         id: "secp224k1",
+        oid: "1.3.132.0.32",
         a: b"00000000000000000000000000000000000000000000000000000000",
         b: b"00000000000000000000000000000000000000000000000000000005",
         p: b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFE56D",
         g: b"04A1455B334DF099DF30FC28A169A467E9E47075A90F7E650EB6B7A45C7E089FED7FBA344282CAFBD6F7E319F7C0B0BD59E2CA4BDB556D61A5",
         n: b"010000000000000000000000000001DCE8D2EC6184CAF0A971769FB1F7",
-        h: b"01"
+        h: b"01",
     },
     EcParam {
-    	id: "secp224r1",
+        id: "secp224r1",
+        oid: "1.3.132.0.33",
         a: b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFFFFFFFFFFFFFFFFFE",
         b: b"B4050A850C04B3ABF54132565044B0B7D7BFD8BA270B39432355FFB4",
         p: b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF000000000000000000000001",
         g: b"04B70E0CBD6BB4BF7F321390B94A03C1D356C21122343280D6115C1D21BD376388B5F723FB4C22DFE6CD4375A05A07476444D5819985007E34",
         n: b"FFFFFFFFFFFFFFFFFFFFFFFFFFFF16A2E0B8F03E13DD29455C5C2A3D",
-        h: b"01"
+        h: b"01",
     },
     EcParam {
         id: "secp256k1",
+        oid: "1.3.132.0.10",
         a: b"0000000000000000000000000000000000000000000000000000000000000000",
         b: b"0000000000000000000000000000000000000000000000000000000000000007",
         p: b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
         g: b"0479BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
         n: b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
-        h: b"01"
+        h: b"01",
+    },
+    EcParam {
+        id: "secp256r1",
+        oid: "1.2.840.10045.3.1.7",
+        a: b"FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFC",
+        b: b"5AC635D8AA3A93E7B3EBBD55769886BC651D06B0CC53B0F63BCE3C3E27D2604B",
+        p: b"FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFF",
+        g: b"046B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C2964FE342E2FE1A7F9B8EE7EB4A7C0F9E162BCE33576B315ECECBB6406837BF51F5",
+        n: b"FFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551",
+        h: b"01",
     },
     EcParam {
         id: "secp384r1",
+        oid: "1.3.132.0.34",
         a: b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFF0000000000000000FFFFFFFC",
         b: b"B3312FA7E23EE7E4988E056BE3F82D19181D9C6EFE8141120314088F5013875AC656398D8A2ED19D2A85C8EDD3EC2AEF",
         p: b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFF0000000000000000FFFFFFFF",
         g: b"04AA87CA22BE8B05378EB1C71EF320AD746E1D3B628BA79B9859F741E082542A385502F25DBF55296C3A545E3872760AB73617DE4A96262C6F5D9E98BF9292DC29F8F41DBD289A147CE9DA3113B5F0B8C00A60B1CE1D7E819D7A431D7C90EA0E5F",
         n: b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFC7634D81F4372DDF581A0DB248B0A77AECEC196ACCC52973",
-        h: b"01"
+        h: b"01",
     },
     EcParam {
-        id: "secp512r1",
+        id: "secp521r1",
+        oid: "1.3.132.0.35",
         a: b"01FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFC",
         b: b"0051953EB9618E1C9A1F929A21A0B68540EEA2DA725B99B315F3B8B489918EF109E156193951EC7E937B1652C0BD3BB1BF073573DF883D2C34F1EF451FD46B503F00",
         p: b"01FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
         g: b"0400C6858E06B70404E9CD9E3ECB662395B4429C648139053FB521F828AF606B4D3DBAA14B5E77EFE75928FE1DC127A2FFA8DE3348B3C1856A429BF97E7E31C2E5BD66011839296A789A3BC0045C8A5FB42C7D1BD998F54449579B446817AFBD17273E662C97EE72995EF42640C550B9013FAD0761353C7086A272C24088BE94769FD16650",
         n: b"01FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFA51868783BF2F966B7FCC0148F709A5D03BB5C9B8899C47AEBB6FB71E91386409",
-        h: b"01"
+        h: b"01",
+    },
+    EcParam {
+        id: "brainpoolP160r1",
+        oid: "1.3.36.3.3.2.8.1.1.1",
+        a: b"340E7BE2A280EB74E2BE61BADA745D97E8F7C300",
+        b: b"1E589A8595423412134FAA2DBDEC95C8D8675E58",
+        p: b"E95E4A5F737059DC60DFC7AD95B3D8139515620F",
+        g: b"04BED5AF16EA3F6A4F62938C4631EB5AF7BDBCDBC31667CB477A1A8EC338F94741669C976316DA6321",
+        n: b"E95E4A5F737059DC60DF5991D45029409E60FC09",
+        h: b"01",
     },
+    EcParam {
+        id: "brainpoolP160t1",
+        oid: "1.3.36.3.3.2.8.1.1.2",
+        a: b"E95E4A5F737059DC60DFC7AD95B3D8139515620C",
+        b: b"7A556B6DAE535B7B51ED2C4D7DAA7A0B5C55F380",
+        p: b"E95E4A5F737059DC60DFC7AD95B3D8139515620F",
+        g: b"04B199B13B9B34EFC1397E64BAEB05ACC265FF2378ADD6718B7C7C1961F0991B842443772152C9E0AD",
+        n: b"E95E4A5F737059DC60DF5991D45029409E60FC09",
+        h: b"01",
+    },
+    EcParam {
+        id: "brainpoolP192r1",
+        oid: "1.3.36.3.3.2.8.1.1.3",
+        a: b"6A91174076B1E0E19C39C031FE8685C1CAE040E5C69A28EF",
+        b: b"469A28EF7C28CCA3DC721D044F4496BCCA7EF4146FBF25C9",
+        p: b"C302F41D932A36CDA7A3463093D18DB78FCE476DE1A86297",
+        g: b"04C0A0647EAAB6A48753B033C56CB0F0900A2F5C4853375FD614B690866ABD5BB88B5F4828C1490002E6773FA2FA299B8F",
+        n: b"C302F41D932A36CDA7A3462F9E9E916B5BE8F1029AC4ACC1",
+        h: b"01",
+    },
+    EcParam {
+        id: "brainpoolP192t1",
+        oid: "1.3.36.3.3.2.8.1.1.4",
+        a: b"C302F41D932A36CDA7A3463093D18DB78FCE476DE1A86294",
+        b: b"13D56FFAEC78681E68F9DEB43B35BEC2FB68542E27897B79",
+        p: b"C302F41D932A36CDA7A3463093D18DB78FCE476DE1A86297",
+        g: b"043AE9E58C82F63C30282E1FE7BBF43FA72C446AF6F4618129097E2C5667C2223A902AB5CA449D0084B7E5B3DE7CCC01C9",
+        n: b"C302F41D932A36CDA7A3462F9E9E916B5BE8F1029AC4ACC1",
+        h: b"01",
+    },
+    EcParam {
+        id: "brainpoolP224r1",
+        oid: "1.3.36.3.3.2.8.1.1.5",
+        a: b"68A5E62CA9CE6C1C299803A6C1530B514E182AD8B0042A59CAD29F43",
+        b: b"2580F63CCFE44138870713B1A92369E33E2135D266DBB372386C400B",
+        p: b"D7C134AA264366862A18302575D1D787B09F075797DA89F57EC8C0FF",
+        g: b"040D9029AD2C7E5CF4340823B2A87DC68C9E4CE3174C1E6EFDEE12C07D58AA56F772C0726F24C6B89E4ECDAC24354B9E99CAA3F6D3761402CD",
+        n: b"D7C134AA264366862A18302575D0FB98D116BC4B6DDEBCA3A5A7939F",
+        h: b"01",
+    },
+    EcParam {
+        id: "brainpoolP224t1",
+        oid: "1.3.36.3.3.2.8.1.1.6",
+        a: b"D7C134AA264366862A18302575D1D787B09F075797DA89F57EC8C0FC",
+        b: b"4B337D934104CD7BEF271BF60CED1ED20DA14C08B3BB64F18A60888D",
+        p: b"D7C134AA264366862A18302575D1D787B09F075797DA89F57EC8C0FF",
+        g: b"046AB1E344CE25FF3896424E7FFE14762ECB49F8928AC0C76029B4D5800374E9F5143E568CD23F3F4D7C0D4B1E41C8CC0D1C6ABD5F1A46DB4C",
+        n: b"D7C134AA264366862A18302575D0FB98D116BC4B6DDEBCA3A5A7939F",
+        h: b"01",
+    },
+    EcParam {
+        id: "brainpoolP256r1",
+        oid: "1.3.36.3.3.2.8.1.1.7",
+        a: b"7D5A0975FC2C3057EEF67530417AFFE7FB8055C126DC5C6CE94A4B44F330B5D9",
+        b: b"26DC5C6CE94A4B44F330B5D9BBD77CBF958416295CF7E1CE6BCCDC18FF8C07B6",
+        p: b"A9FB57DBA1EEA9BC3E660A909D838D726E3BF623D52620282013481D1F6E5377",
+        g: b"048BD2AEB9CB7E57CB2C4B482FFC81B7AFB9DE27E1E3BD23C23A4453BD9ACE3262547EF835C3DAC4FD97F8461A14611DC9C27745132DED8E545C1D54C72F046997",
+        n: b"A9FB57DBA1EEA9BC3E660A909D838D718C397AA3B561A6F7901E0E82974856A7",
+        h: b"01",
+    },
+    EcParam {
+        id: "brainpoolP256t1",
+        oid: "1.3.36.3.3.2.8.1.1.8",
+        a: b"A9FB57DBA1EEA9BC3E660A909D838D726E3BF623D52620282013481D1F6E5374",
+        b: b"662C61C430D84EA4FE66A7733D0B76B7BF93EBC4AF2F49256AE58101FEE92B04",
+        p: b"A9FB57DBA1EEA9BC3E660A909D838D726E3BF623D52620282013481D1F6E5377",
+        g: b"04A3E8EB3CC1CFE7B7732213B23A656149AFA142C47AAFBC2B79A191562E1305F42D996C823439C56D7F7B22E14644417E69BCB6DE39D027001DABE8F35B25C9BE",
+        n: b"A9FB57DBA1EEA9BC3E660A909D838D718C397AA3B561A6F7901E0E82974856A7",
+        h: b"01",
+    },
+    EcParam {
+        id: "brainpoolP320r1",
+        oid: "1.3.36.3.3.2.8.1.1.9",
+        a: b"3EE30B568FBAB0F883CCEBD46D3F3BB8A2A73513F5EB79DA66190EB085FFA9F492F375A97D860EB4",
+        b: b"520883949DFDBC42D3AD198640688A6FE13F41349554B49ACC31DCCD884539816F5EB4AC8FB1F1A6",
+        p: b"D35E472036BC4FB7E13C785ED201E065F98FCFA6F6F40DEF4F92B9EC7893EC28FCD412B1F1B32E27",
+        g: b"0443BD7E9AFB53D8B85289BCC48EE5BFE6F20137D10A087EB6E7871E2A10A599C710AF8D0D39E2061114FDD05545EC1CC8AB4093247F77275E0743FFED117182EAA9C77877AAAC6AC7D35245D1692E8EE1",
+        n: b"D35E472036BC4FB7E13C785ED201E065F98FCFA5B68F12A32D482EC7EE8658E98691555B44C59311",
+        h: b"01",
+    },
+    EcParam {
+        id: "brainpoolP320t1",
+        oid: "1.3.36.3.3.2.8.1.1.10",
+        a: b"D35E472036BC4FB7E13C785ED201E065F98FCFA6F6F40DEF4F92B9EC7893EC28FCD412B1F1B32E24",
+        b: b"A7F561E038EB1ED560B3D147DB782013064C19F27ED27C6780AAF77FB8A547CEB5B4FEF422340353",
+        p: b"D35E472036BC4FB7E13C785ED201E065F98FCFA6F6F40DEF4F92B9EC7893EC28FCD412B1F1B32E27",
+        g: b"04925BE9FB01AFC6FB4D3E7D4990010F813408AB106C4F09CB7EE07868CC136FFF3357F624A21BED5263BA3A7A27483EBF6671DBEF7ABB30EBEE084E58A0B077AD42A5A0989D1EE71B1B9BC0455FB0D2C3",
+        n: b"D35E472036BC4FB7E13C785ED201E065F98FCFA5B68F12A32D482EC7EE8658E98691555B44C59311",
+        h: b"01",
+    },
+    EcParam {
+        id: "brainpoolP384r1",
+        oid: "1.3.36.3.3.2.8.1.1.11",
+        a: b"7BC382C63D8C150C3C72080ACE05AFA0C2BEA28E4FB22787139165EFBA91F90F8AA5814A503AD4EB04A8C7DD22CE2826",
+        b: b"04A8C7DD22CE28268B39B55416F0447C2FB77DE107DCD2A62E880EA53EEB62D57CB4390295DBC9943AB78696FA504C11",
+        p: b"8CB91E82A3386D280F5D6F7E50E641DF152F7109ED5456B412B1DA197FB71123ACD3A729901D1A71874700133107EC53",
+        g: b"041D1C64F068CF45FFA2A63A81B7C13F6B8847A3E77EF14FE3DB7FCAFE0CBD10E8E826E03436D646AAEF87B2E247D4AF1E8ABE1D7520F9C2A45CB1EB8E95CFD55262B70B29FEEC5864E19C054FF99129280E4646217791811142820341263C5315",
+        n: b"8CB91E82A3386D280F5D6F7E50E641DF152F7109ED5456B31F166E6CAC0425A7CF3AB6AF6B7FC3103B883202E9046565",
+        h: b"01",
+    },
+    EcParam {
+        id: "brainpoolP384t1",
+        oid: "1.3.36.3.3.2.8.1.1.12",
+        a: b"8CB91E82A3386D280F5D6F7E50E641DF152F7109ED5456B412B1DA197FB71123ACD3A729901D1A71874700133107EC50",
+        b: b"7F519EADA7BDA81BD826DBA647910F8C4B9346ED8CCDC64E4B1ABD11756DCE1D2074AA263B88805CED70355A33B471EE",
+        p: b"8CB91E82A3386D280F5D6F7E50E641DF152F7109ED5456B412B1DA197FB71123ACD3A729901D1A71874700133107EC53",
+        g: b"0418DE98B02DB9A306F2AFCD7235F72A819B80AB12EBD653172476FECD462AABFFC4FF191B946A5F54D8D0AA2F418808CC25AB056962D30651A114AFD2755AD336747F93475B7A1FCA3B88F2B6A208CCFE469408584DC2B2912675BF5B9E582928",
+        n: b"8CB91E82A3386D280F5D6F7E50E641DF152F7109ED5456B31F166E6CAC0425A7CF3AB6AF6B7FC3103B883202E9046565",
+        h: b"01",
+    },
+    EcParam {
+        id: "brainpoolP512r1",
+        oid: "1.3.36.3.3.2.8.1.1.13",
+        a: b"7830A3318B603B89E2327145AC234CC594CBDD8D3DF91610A83441CAEA9863BC2DED5D5AA8253AA10A2EF1C98B9AC8B57F1117A72BF2C7B9E7C1AC4D77FC94CA",
+        b: b"3DF91610A83441CAEA9863BC2DED5D5AA8253AA10A2EF1C98B9AC8B57F1117A72BF2C7B9E7C1AC4D77FC94CADC083E67984050B75EBAE5DD2809BD638016F723",
+        p: b"AADD9DB8DBE9C48B3FD4E6AE33C9FC07CB308DB3B3C9D20ED6639CCA703308717D4D9B009BC66842AECDA12AE6A380E62881FF2F2D82C68528AA6056583A48F3",
+        g: b"0481AEE4BDD82ED9645A21322E9C4C6A9385ED9F70B5D916C1B43B62EEF4D0098EFF3B1F78E2D0D48D50D1687B93B97D5F7C6D5047406A5E688B352209BCB9F8227DDE385D566332ECC0EABFA9CF7822FDF209F70024A57B1AA000C55B881F8111B2DCDE494A5F485E5BCA4BD88A2763AED1CA2B2FA8F0540678CD1E0F3AD80892",
+        n: b"AADD9DB8DBE9C48B3FD4E6AE33C9FC07CB308DB3B3C9D20ED6639CCA70330870553E5C414CA92619418661197FAC10471DB1D381085DDADDB58796829CA90069",
+        h: b"01",
+    },
+    EcParam {
+        id: "brainpoolP512t1",
+        oid: "1.3.36.3.3.2.8.1.1.14",
+        a: b"AADD9DB8DBE9C48B3FD4E6AE33C9FC07CB308DB3B3C9D20ED6639CCA703308717D4D9B009BC66842AECDA12AE6A380E62881FF2F2D82C68528AA6056583A48F0",
+        b: b"7CBBBCF9441CFAB76E1890E46884EAE321F70C0BCB4981527897504BEC3E36A62BCDFA2304976540F6450085F2DAE145C22553B465763689180EA2571867423E",
+        p: b"AADD9DB8DBE9C48B3FD4E6AE33C9FC07CB308DB3B3C9D20ED6639CCA703308717D4D9B009BC66842AECDA12AE6A380E62881FF2F2D82C68528AA6056583A48F3",
+        g: b"04640ECE5C12788717B9C1BA06CBC2A6FEBA85842458C56DDE9DB1758D39C0313D82BA51735CDB3EA499AA77A7D6943A64F7A3F25FE26F06B51BAA2696FA9035DA5B534BD595F5AF0FA2C892376C84ACE1BB4E3019B71634C01131159CAE03CEE9D9932184BEEF216BD71DF2DADF86A627306ECFF96DBB8BACE198B61E00F8B332",
+        n: b"AADD9DB8DBE9C48B3FD4E6AE33C9FC07CB308DB3B3C9D20ED6639CCA70330870553E5C414CA92619418661197FAC10471DB1D381085DDADDB58796829CA90069",
+        h: b"01",
+    },
+];
+
+///   NIST (FIPS 186-4) curve names
+static CURVE_ALIASES: [(&str, &str); 5] = [
+    ("P-192", "secp192r1"),
+    ("P-224", "secp224r1"),
+    ("P-256", "secp256r1"),
+    ("P-384", "secp384r1"),
+    ("P-521", "secp521r1"),
 ];
 
 impl EcParam<'_> {
-    pub fn new(ecid: &str) -> EcParam {
-        let mut id: &str = CURVE_PARAMS[0].id;
-        let mut ai: &[u8] = CURVE_PARAMS[0].a;
-        let mut bi: &[u8] = CURVE_PARAMS[0].b;
-        let mut pi: &[u8] = CURVE_PARAMS[0].p;
-        let mut gi: &[u8] = CURVE_PARAMS[0].g;
-        let mut ni: &[u8] = CURVE_PARAMS[0].n;
-        let mut hi: &[u8] = CURVE_PARAMS[0].h;
-
-        for prm in CURVE_PARAMS.iter() {
-            if prm.id == ecid {
-                id = prm.id;
-                ai = prm.a;
-                bi = prm.b;
-                pi = prm.p;
-                gi = prm.g;
-                ni = prm.n;
-                hi = prm.h;
-            }
-        }
-        EcParam {
-            id,
-            a: ai,
-            b: bi,
-            p: pi,
-            g: gi,
-            n: ni,
-            h: hi,
-        }
+    /// Curve parameters by id (SEC 2 / Brainpool name or NIST alias)
+    /// - panics if the curve is unknown (see try_new)
+    pub fn new(ecid: &str) -> EcParam<'static> {
+        Self::try_new(ecid).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Curve parameters by id (SEC 2 / Brainpool name or NIST alias)
+    pub fn try_new(ecid: &str) -> Result<EcParam<'static>, UnknownCurve> {
+        Self::find(ecid)
+            .copied()
+            .ok_or_else(|| UnknownCurve(ecid.to_string()))
+    }
+
+    /// Supported curve ids (NIST aliases such as "P-256" are also accepted)
+    pub fn curve_ids() -> Vec<&'static str> {
+        CURVE_PARAMS.iter().map(|prm| prm.id).collect()
+    }
+
+    /// Curve parameters by object identifier (e.g. "1.3.132.0.10")
+    pub fn from_oid(oid: &str) -> Option<EcParam<'static>> {
+        CURVE_PARAMS.iter().find(|prm| prm.oid == oid).copied()
+    }
+
+    fn find(ecid: &str) -> Option<&'static EcParam<'static>> {
+        let id = CURVE_ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == ecid)
+            .map_or(ecid, |(_, id)| *id);
+        CURVE_PARAMS.iter().find(|prm| prm.id == id)
     }
 
     pub fn get_ecid(&self) -> &str {
         self.id
     }
 
-    pub fn set_parameters(&mut self, id: &str) {
-        for prm in CURVE_PARAMS.iter() {
-            if prm.id == id {
-                self.id = prm.id;
-                self.a = prm.a;
-                self.b = prm.b;
-                self.p = prm.p;
-                self.g = prm.g;
-                self.n = prm.n;
-                self.h = prm.h;
-            }
-        }
+    pub fn get_oid(&self) -> &str {
+        self.oid
+    }
+
+    pub fn set_parameters(&mut self, id: &str) -> Result<(), UnknownCurve> {
+        *self = Self::try_new(id)?;
+        Ok(())
     }
 
     pub fn check_parameters(&self, id: &str) -> bool {
-        for prm in CURVE_PARAMS.iter() {
-            if prm.id == id {
-                return true;
-            }
-        }
-        false
+        Self::find(id).is_some()
     }
 }
 
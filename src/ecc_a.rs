@@ -3,9 +3,12 @@
 //!
 //  Written by blanclux
 //  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
-use super::ec_param::{EcParam, ParamOp};
-use super::number::Number;
-use super::{EcAxis, EcOp, Point};
+use std::sync::OnceLock;
+
+use super::ec_param::{check_field, EcParam, ParamOp, UnknownCurve};
+use super::field::{FieldElement, PrimeField};
+use super::number::{fits_field, Number};
+use super::{fixed_base_table, mul_fixed_base, BaseTable, EcAxis, EcOp, Point, WeierstrassOp};
 
 /// Elliptic curve  (Affine)
 #[derive(Clone)]
@@ -17,6 +20,10 @@ pub struct EcpA<T: Number> {
     pub h: T,
     pub p_zero: Point<T>,
     pub p_g: Point<T>,
+    /// GF(p)
+    field: PrimeField<T>,
+    /// Fixed-base table of p_g (built on first mul_base)
+    base: OnceLock<BaseTable<Point<T>>>,
 }
 
 impl<T: Number> EcOp<T> for EcpA<T> {
@@ -47,10 +54,9 @@ impl<T: Number> EcOp<T> for EcpA<T> {
             return true;
         }
 
-        // y^2 = x^3 + ax + b = (x^2 + a) * x + b
-        let l = p_p.y.mul_ref(&p_p.y) % &self.p;
-        let r = ((p_p.x.mul_ref(&p_p.x) + &self.a) * &p_p.x + &self.b) % &self.p;
-        l == r // return bool
+        // y^2 = x^3 + ax + b
+        let f = &self.field;
+        f.elem(&p_p.y).square() == self.rhs(&f.elem(&p_p.x)) // return bool
     }
 
     /// Check equality
@@ -66,25 +72,23 @@ impl<T: Number> EcOp<T> for EcpA<T> {
     fn normalize(&self, _p_p: &mut Point<T>) {}
 
     /// Find Point<T>s on curve at x
-    fn point_from_x(&self, x: &T, yt: u32) -> Point<T> {
-        assert!(x < &self.p);
-
-        // y^2 = x^3 + ax + b = (x^2 + a) * x + b
-        let y2 = Number::mod_cal(&((x.mul_ref(x) + &self.a) * x + &self.b), &self.p);
-        let mut y = Number::mod_sqrt(&y2, &self.p);
+    /// - returns: (x, y, 1) with y = yt mod 2,  None if there is no point at x or x is not in [0, p)
+    fn point_from_x(&self, x: &T, yt: u32) -> Option<Point<T>> {
+        if x < &T::zero() || x >= &self.p {
+            return None;
+        }
 
-        //        let bit0 = y % T::from(2u32);
-        let bit0 = y.clone() % T::from(2u32);
-        if bit0 != T::from(yt) {
-            y = self.p.sub_ref(&y);
+        let mut y = self.rhs(&self.field.elem(x)).sqrt()?;
+        if y.is_odd() != (yt & 1 == 1) {
+            y = -y;
         }
 
-        Point {
+        Some(Point {
             axis: EcAxis::Affine,
             x: x.clone(),
-            y,
+            y: y.value(),
             z: T::one(),
-        }
+        })
     }
 
     /// Find point on curve at (x, y)
@@ -97,149 +101,91 @@ impl<T: Number> EcOp<T> for EcpA<T> {
         }
     }
 
-    /// OS2ECPP: Decode a point on this curve which has been encoded using point
-    /// compression(X9.62) returning the point.
-    #[allow(unused_assignments)]
-    fn decode_point(&self, enc: &[u8]) -> Point<T> {
-        if enc.len() == 1 {
-            if enc[0] == 0 {
-                return self.get_zero();
-            } else {
-                panic!("Invalid point compression");
-            }
-        }
-
-        let mut x = T::zero();
-        let mut y = T::zero();
-        match enc[0] {
-            // compressed
-            0x02 | 0x03 => {
-                let ln = enc.len() - 1;
-                let xb = &enc[1..ln + 1];
-                x = T::from_bytes_be(xb);
-                let yt = enc[0] & 1;
-                return self.point_from_x(&x, yt.into());
-            }
-            // uncompressed
-            0x04 => {
-                let ln = (enc.len() - 1) / 2;
-                let xb = &enc[1..(ln + 1)];
-                let yb = &enc[(ln + 1)..enc.len()];
-                x = T::from_bytes_be(xb);
-                y = T::from_bytes_be(yb);
-            }
-            _ => panic!("Invalid point compression"),
-        }
-        Point {
-            axis: EcAxis::Affine,
-            x,
-            y,
-            z: T::one(),
-        }
-    }
-
-    /// Returns the field element encoded with point compression.
-    fn get_encoded(&self, p: &Point<T>) -> Vec<u8> {
-        let q = self.to_affine(p);
-
-        let y = q.y;
-        let x = q.x;
-        // y~ = y mod 2
-        let yt: T = y % T::from(2u32);
-        let hd = if yt.is_one() { 0x03 } else { 0x02 };
-
-        let xb = x.to_bytes_be();
-        let mut po = vec![0u8; xb.len() + 1];
-        po[0] = hd;
-        po[1..].copy_from_slice(&xb[..xb.len()]);
-
-        po
-    }
-
     /// Negate P
     fn negate(&self, p_p: &Point<T>) -> Point<T> {
         Point {
             axis: EcAxis::Affine,
             x: p_p.x.clone(),
-            y: self.p.clone() - p_p.y.clone(),
+            y: (-self.field.elem(&p_p.y)).value(),
             z: T::one(),
         }
     }
 
     /// EC Point<T> double : Q = 2 * P
     fn double(&self, p_p: &Point<T>) -> Point<T> {
-        let (x, y, mut r): (T, T, T);
-
-        if p_p.is_zero() || p_p.y == T::zero() {
+        let f = &self.field;
+        let (x, y) = (f.elem(&p_p.x), f.elem(&p_p.y));
+        if p_p.is_zero() || y.is_zero() {
             return self.p_zero.clone();
         }
 
-        r = (T::from(3u32) * &p_p.x * &p_p.x + &self.a) % &self.p;
-        r = Number::mod_inv(&(T::from(2u32) * &p_p.y), &self.p) * &r % &self.p;
-        x = Number::mod_cal(&(r.mul_ref(&r) % &self.p - T::from(2u32) * &p_p.x), &self.p);
-        y = Number::mod_cal(&((p_p.x.sub_ref(&x)) * &r % &self.p - &p_p.y), &self.p);
-
-        Point {
-            axis: EcAxis::Affine,
-            x,
-            y,
-            z: T::one(),
-        }
+        // r = (3x^2 + a) / 2y
+        let x2 = x.square();
+        let r = &(&(&x2.double() + &x2) + &f.elem(&self.a)) * &y.double().inv().unwrap();
+        self.add_line(&r, &x, &y, &x)
     }
 
     /// EC point add : Q = P1 + P2
     fn add(&self, p_p1: &Point<T>, p_p2: &Point<T>) -> Point<T> {
-        let (x, y, mut r): (T, T, T);
-
         if p_p1.is_zero() {
             return p_p2.clone();
         }
         if p_p2.is_zero() {
             return p_p1.clone();
         }
-        if p_p1.x == p_p2.x && (p_p1.y != p_p2.y || p_p1.y == T::zero()) {
-            // P1 + (-P1) == 0
-            return self.p_zero.clone();
-        }
-        if p_p1.x == p_p2.x {
+        let f = &self.field;
+        let (x1, y1) = (f.elem(&p_p1.x), f.elem(&p_p1.y));
+        let (x2, y2) = (f.elem(&p_p2.x), f.elem(&p_p2.y));
+
+        if x1 == x2 {
+            if y1 != y2 || y1.is_zero() {
+                // P1 + (-P1) == 0
+                return self.p_zero.clone();
+            }
             // double (2 * P)
-            r = (T::from(3u32) * &p_p1.x * &p_p1.x + &self.a) % &self.p;
-            r = Number::mod_inv(&(T::from(2u32) * &p_p1.y), &self.p) * &r % &self.p;
-        } else {
-            r = ((p_p1.y.sub_ref(&p_p2.y)) * Number::mod_inv(&(p_p1.x.sub_ref(&p_p2.x)), &self.p))
-                % &self.p;
+            return self.double(p_p1);
         }
-        x = Number::mod_cal(&(r.mul_ref(&r) - &p_p1.x - &p_p2.x), &self.p);
-        y = Number::mod_cal(&((p_p1.x.sub_ref(&x)) * &r - &p_p1.y), &self.p);
+        // r = (y1 - y2) / (x1 - x2)
+        let r = &(&y1 - &y2) * &(&x1 - &x2).inv().unwrap();
+        self.add_line(&r, &x1, &y1, &x2)
+    }
 
-        Point {
-            axis: EcAxis::Affine,
-            x,
-            y,
-            z: T::one(),
+    /// Multiply (k * G) (fixed-base table)
+    fn mul_base(&self, k: &T) -> Point<T> {
+        if !self.n.test_bit(0) {
+            return self.mul_ct(&self.p_g, k);
         }
+        let table = self.base.get_or_init(|| {
+            fixed_base_table(
+                &self.p_g,
+                self.n.bit_len(),
+                |p1, p2| self.add(p1, p2),
+                |p| self.double(p),
+            )
+        });
+        mul_fixed_base(
+            table,
+            &Number::mod_cal(k, &self.n),
+            &self.n,
+            |p1, p2| self.add(p1, p2),
+            |p| self.negate(p),
+        )
     }
 
     /// Generate a random point
     fn gen_point(&self) -> Point<T> {
-        let (mut x, mut y, mut y2): (T, T, T);
-        let pu = self.p.clone();
+        let f = &self.field;
+        let p1 = self.p.sub_ref(&T::one());
 
         loop {
-            x = Number::gen_rand(&T::one(), &(pu.sub_ref(&T::one())));
-
-            // y^2 = x^3 + ax + b = (x^2 + a) * x + b
-            y2 = Number::mod_cal(&((x.mul_ref(&x) + &self.a) * &x + &self.b), &self.p);
-            y = Number::mod_sqrt(&y2, &self.p);
-
-            let pt = Point {
-                axis: EcAxis::Affine,
-                x,
-                y,
-                z: T::one(),
-            };
-            if self.on_curve(&pt) {
-                return pt;
+            let x = Number::gen_rand(&T::one(), &p1);
+            if let Some(y) = self.rhs(&f.elem(&x)).sqrt() {
+                return Point {
+                    axis: EcAxis::Affine,
+                    x,
+                    y: y.value(),
+                    z: T::one(),
+                };
             }
         }
     }
@@ -263,6 +209,18 @@ impl<T: Number> EcOp<T> for EcpA<T> {
         self.p_g.clone()
     }
 
+    /// Get prime p
+    #[inline]
+    fn get_prime(&self) -> T {
+        self.p.clone()
+    }
+
+    /// Get coefficient a
+    #[inline]
+    fn get_a(&self) -> T {
+        self.a.clone()
+    }
+
     /// Get order
     #[inline]
     fn get_order(&self) -> T {
@@ -281,11 +239,71 @@ impl<T: Number> EcOp<T> for EcpA<T> {
     }
 }
 
+impl<T: Number> WeierstrassOp<T> for EcpA<T> {
+    /// Get coefficient b
+    #[inline]
+    fn get_b(&self) -> T {
+        self.b.clone()
+    }
+}
+
 impl<T: Number> EcpA<T> {
+    /// x^3 + ax + b = (x^2 + a) * x + b
+    fn rhs<'a>(&'a self, x: &FieldElement<'a, T>) -> FieldElement<'a, T> {
+        let f = &self.field;
+        &(&(&x.square() + &f.elem(&self.a)) * x) + &f.elem(&self.b)
+    }
+
+    /// Third point on the line of slope r through (x1, y1):
+    /// x3 = r^2 - x1 - x2,  y3 = (x1 - x3) * r - y1
+    fn add_line(
+        &self,
+        r: &FieldElement<'_, T>,
+        x1: &FieldElement<'_, T>,
+        y1: &FieldElement<'_, T>,
+        x2: &FieldElement<'_, T>,
+    ) -> Point<T> {
+        let x3 = &(&r.square() - x1) - x2;
+        let y3 = &(&(x1 - &x3) * r) - y1;
+
+        Point {
+            axis: EcAxis::Affine,
+            x: x3.value(),
+            y: y3.value(),
+            z: T::one(),
+        }
+    }
+
+    /// Curve by id
+    /// - panics if the curve is unknown (see try_new)
     pub fn new(ec_name: &str) -> EcpA<T> {
-        let ec = EcParam::new(ec_name);
+        Self::try_new(ec_name).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Curve by id,  Err if the curve is unknown or its field does not fit T
+    /// - uses the special-form reduction of the curve prime if there is one
+    pub fn try_new(ec_name: &str) -> Result<EcpA<T>, UnknownCurve> {
+        let ec = EcParam::try_new(ec_name)?;
+        check_field(ec_name, &ParamOp::<T>::get_prime(&ec))?;
+        Ok(Self::from_param(&ec))
+    }
+
+    /// Curve from parameters (built-in EcParam or user-supplied CurveParam)
+    /// - uses the special-form reduction of the prime if there is one
+    pub fn from_param<P: ParamOp<T>>(ec: &P) -> EcpA<T> {
+        Self::with_field(ec, PrimeField::for_prime(&ec.get_prime()))
+    }
 
+    /// Curve from parameters over the given field (e.g. PrimeField::new for Montgomery form)
+    /// - panics if the field does not fit T (see number::fits_field)
+    pub fn with_field<P: ParamOp<T>>(ec: &P, field: PrimeField<T>) -> EcpA<T> {
+        assert!(
+            fits_field(field.get_prime()),
+            "field too large for the number type"
+        );
         EcpA {
+            field,
+            base: OnceLock::new(),
             a: ec.get_a(),
             b: ec.get_b(),
             p: ec.get_prime(),
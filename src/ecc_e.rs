@@ -0,0 +1,609 @@
+//!
+//! Elliptic Curve Calculation (Twisted Edwards - Extended coordinates)
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+use std::sync::OnceLock;
+
+use super::ec_param::{check_field, UnknownCurve};
+use super::ed_param::EdParam;
+use super::field::{FieldElement, PrimeField};
+use super::number::Number;
+use super::{
+    fixed_base_table, get_wnaf_size, mul_fixed_base, mul_ladder, mul_multi_wnaf, mul_window,
+    mul_wnaf, odd_multiples, BaseTable, EcAxis, EcError, EcOp, Point, PointFormat, Select,
+};
+
+/// Twisted Edwards curve  a * x^2 + y^2 = 1 + d * x^2 * y^2  (Extended - X:Y:Z:T)
+/// - the addition law is complete for a square a and a non-square d (edwards25519, edwards448)
+/// - the neutral element is (0, 1)
+/// - points are (X:Y:Z) (EcAxis::Edwards);  the arithmetic runs in extended coordinates,
+///   T = XY/Z is restored when a point enters the field representation
+#[derive(Clone)]
+pub struct EcpE<T: Number> {
+    pub id: &'static str,
+    pub a: T,
+    pub d: T,
+    pub p: T,
+    pub n: T,
+    pub h: T,
+    pub p_zero: Point<T>,
+    pub p_g: Point<T>,
+    /// GF(p) (Montgomery form or special-form reduction)
+    field: PrimeField<T>,
+    /// a and d in the field representation
+    a_m: T,
+    d_m: T,
+    /// a = -1 mod p
+    a_m1: bool,
+    /// Fixed-base table of p_g (built on first mul_base)
+    base: OnceLock<BaseTable<ExtPoint<T>>>,
+}
+
+/// Extended coordinates (X:Y:Z:T),  T = XY/Z  (field representation)
+#[derive(Clone)]
+struct ExtPoint<T> {
+    x: T,
+    y: T,
+    z: T,
+    t: T,
+}
+
+impl<T: Number> Select for ExtPoint<T> {
+    fn select(p1: &ExtPoint<T>, p2: &ExtPoint<T>, choice: bool) -> ExtPoint<T> {
+        ExtPoint {
+            x: T::select(&p1.x, &p2.x, choice),
+            y: T::select(&p1.y, &p2.y, choice),
+            z: T::select(&p1.z, &p2.z, choice),
+            t: T::select(&p1.t, &p2.t, choice),
+        }
+    }
+}
+
+impl<T: Number> EcOp<T> for EcpE<T> {
+    /// Get zero point (0 : 1 : 1)
+    fn get_zero(&self) -> Point<T> {
+        self.p_zero.clone()
+    }
+
+    /// Is zero point ?  (X = 0 and Y = Z, in either representation)
+    #[inline]
+    fn is_zero(&self, p_p: &Point<T>) -> bool {
+        p_p.is_zero()
+    }
+
+    /// Copy point
+    fn set(&self, p_p1: &mut Point<T>, p_p2: &Point<T>) {
+        *p_p1 = p_p2.clone();
+    }
+
+    /// Point P is on curve ?
+    fn on_curve(&self, p_p: &Point<T>) -> bool {
+        let f = &self.field;
+        let (x, y, z) = self.elems(p_p);
+        if z.is_zero() {
+            return false;
+        }
+        // (a * X^2 + Y^2) * Z^2 = Z^4 + d * X^2 * Y^2
+        let (x2, y2, z2) = (x.square(), y.square(), z.square());
+        let l = &(&(&f.elem(&self.a) * &x2) + &y2) * &z2;
+        let r = &z2.square() + &(&f.elem(&self.d) * &(&x2 * &y2));
+        l == r
+    }
+
+    /// Convert to (x, y, 1)
+    fn to_affine(&self, p_p: &Point<T>) -> Point<T> {
+        let mut p_r = p_p.clone();
+        self.normalize(&mut p_r);
+        p_r
+    }
+
+    fn normalize(&self, p_p: &mut Point<T>) {
+        if p_p.z.is_one() {
+            return;
+        }
+        let (x, y, z) = self.elems(p_p);
+
+        // x = X / Z,  y = Y / Z
+        let zi = z.inv().unwrap();
+        p_p.x = (&x * &zi).value();
+        p_p.y = (&y * &zi).value();
+        p_p.z = T::one();
+    }
+
+    /// Check equality
+    fn equals(&self, p1: &Point<T>, p2: &Point<T>) -> bool {
+        let (x1, y1, z1) = self.elems(p1);
+        let (x2, y2, z2) = self.elems(p2);
+
+        // X1 * Z2 = X2 * Z1,  Y1 * Z2 = Y2 * Z1 ?
+        &x1 * &z2 == &x2 * &z1 && &y1 * &z2 == &y2 * &z1
+    }
+
+    /// Negate P = (-X : Y : Z)
+    fn negate(&self, p_p: &Point<T>) -> Point<T> {
+        Point {
+            axis: EcAxis::Edwards,
+            x: (-self.field.elem(&p_p.x)).value(),
+            y: p_p.y.clone(),
+            z: p_p.z.clone(),
+        }
+    }
+
+    /// EC point double : Q = 2 * P
+    fn double(&self, p_p: &Point<T>) -> Point<T> {
+        self.to_normal(&self.double_m(&self.to_field(p_p)))
+    }
+
+    /// EC point add : Q = P1 + P2
+    fn add(&self, p_p1: &Point<T>, p_p2: &Point<T>) -> Point<T> {
+        self.to_normal(&self.add_m(&self.to_field(p_p1), &self.to_field(p_p2)))
+    }
+
+    /// Multiply (k * P)
+    /// Sliding window multiplication (in the field representation)
+    fn mul(&self, p_p: &Point<T>, k: &T) -> Point<T> {
+        let p_r = mul_window(
+            &self.to_field(p_p),
+            k,
+            self.zero_m(),
+            |p1, p2| self.add_m(p1, p2),
+            |p| self.double_m(p),
+        );
+        self.to_normal(&p_r)
+    }
+
+    /// Multiply (k * P) (wNAF, in the field representation)
+    fn mul_wnaf(&self, p_p: &Point<T>, k: &T) -> Point<T> {
+        let w = get_wnaf_size(k);
+        let table = odd_multiples(
+            &self.to_field(p_p),
+            w,
+            |p1, p2| self.add_m(p1, p2),
+            |p| self.double_m(p),
+        );
+        let p_r = mul_wnaf(
+            &table,
+            k,
+            w,
+            self.zero_m(),
+            |p1, p2| self.add_m(p1, p2),
+            |p| self.double_m(p),
+            |p| self.negate_m(p),
+        );
+        self.to_normal(&p_r)
+    }
+
+    /// Multiply and add (k * P + l * Q) (interleaved wNAF, in the field representation)
+    fn mul2(&self, p_p: &Point<T>, k: &T, p_q: &Point<T>, l: &T) -> Point<T> {
+        self.mul_multi(&[(p_p, k), (p_q, l)])
+    }
+
+    /// Multi-scalar multiplication (interleaved wNAF, in the field representation)
+    fn mul_multi(&self, terms: &[(&Point<T>, &T)]) -> Point<T> {
+        let points: Vec<ExtPoint<T>> = terms.iter().map(|(p, _)| self.to_field(p)).collect();
+        let terms: Vec<(&ExtPoint<T>, &T)> =
+            points.iter().zip(terms).map(|(p, t)| (p, t.1)).collect();
+        let p_r = mul_multi_wnaf(
+            &terms,
+            self.zero_m(),
+            |p1, p2| self.add_m(p1, p2),
+            |p| self.double_m(p),
+            |p| self.negate_m(p),
+        );
+        self.to_normal(&p_r)
+    }
+
+    /// Multiply (k * G) (fixed-base table, in the field representation)
+    fn mul_base(&self, k: &T) -> Point<T> {
+        if !self.n.test_bit(0) {
+            return self.mul_ct(&self.p_g, k);
+        }
+        let table = self.base.get_or_init(|| {
+            fixed_base_table(
+                &self.to_field(&self.p_g),
+                self.n.bit_len(),
+                |p1, p2| self.add_m(p1, p2),
+                |p| self.double_m(p),
+            )
+        });
+        let p_r = mul_fixed_base(
+            table,
+            &Number::mod_cal(k, &self.n),
+            &self.n,
+            |p1, p2| self.add_m(p1, p2),
+            |p| self.negate_m(p),
+        );
+        self.to_normal(&p_r)
+    }
+
+    /// Multiply (k * P) (Binary method, in the field representation)
+    fn mul_bin(&self, p_p: &Point<T>, k: &T) -> Point<T> {
+        let mut p_r = self.zero_m();
+        let mut p_s = self.to_field(p_p);
+
+        for s in 0..k.bit_len() {
+            if k.test_bit(s) {
+                p_r = self.add_m(&p_r, &p_s);
+            }
+            p_s = self.double_m(&p_s);
+        }
+        self.to_normal(&p_r)
+    }
+
+    /// Multiply (k * P) (Montgomery ladder, in the field representation)
+    fn mul_ct(&self, p_p: &Point<T>, k: &T) -> Point<T> {
+        let bits = self.n.bit_len().max(k.bit_len());
+        let p_r = mul_ladder(
+            &self.to_field(p_p),
+            k,
+            bits,
+            self.zero_m(),
+            |p1, p2| self.add_m(p1, p2),
+            |p| self.double_m(p),
+        );
+        self.to_normal(&p_r)
+    }
+
+    /// OS2ECPP for Edwards curves:  RFC 8032 decoding (see decode_ed),  no subgroup check
+    /// (try_decode_point adds the subgroup check)
+    fn try_decode_point_partial(&self, enc: &[u8]) -> Result<Point<T>, EcError> {
+        self.decode_ed(enc)
+    }
+
+    /// Partial public key validation: coordinates in range, on the Edwards curve
+    /// (check_point adds the subgroup check)
+    fn check_point_partial(&self, p_p: &Point<T>) -> Result<(), EcError> {
+        let q = self.to_affine(p_p);
+        if q.x < T::zero() || q.x >= self.p || q.y < T::zero() || q.y >= self.p {
+            return Err(EcError::CoordinateOutOfRange);
+        }
+        if !self.on_curve(&q) {
+            return Err(EcError::NotOnCurve);
+        }
+        Ok(())
+    }
+
+    /// Generate a random point
+    fn gen_point(&self) -> Point<T> {
+        loop {
+            let y = Number::gen_rand(&T::zero(), &self.p);
+            if let Some(p_p) = self.point_from_y(&y, 0) {
+                return p_p;
+            }
+        }
+    }
+
+    /// Find points on curve at x
+    /// - returns: (x, y, 1) with y = yt mod 2,  None if there is no point at x or x is not in [0, p)
+    fn point_from_x(&self, x: &T, yt: u32) -> Option<Point<T>> {
+        if x < &T::zero() || x >= &self.p {
+            return None;
+        }
+        let f = &self.field;
+
+        // y^2 = (1 - a * x^2) / (1 - d * x^2)
+        let x = f.elem(x);
+        let x2 = x.square();
+        let u = &f.one() - &(&f.elem(&self.a) * &x2);
+        let v = &f.one() - &(&f.elem(&self.d) * &x2);
+        let mut y = (&u * &v.inv()?).sqrt()?;
+        if y.is_odd() != (yt & 1 == 1) {
+            y = -y;
+        }
+        Some(self.point_from_xy(&x.value(), &y.value()))
+    }
+
+    /// Find points on curve at (x, y)
+    fn point_from_xy(&self, x: &T, y: &T) -> Point<T> {
+        Point {
+            axis: EcAxis::Edwards,
+            x: x.clone(),
+            y: y.clone(),
+            z: T::one(),
+        }
+    }
+
+    /// Order of point P
+    fn calc_order(&self, p_p: &Point<T>) -> T {
+        let mut m = T::one();
+
+        while m < self.p.add_ref(&T::one()) {
+            if self.mul(p_p, &m).is_zero() {
+                return m;
+            }
+            m = m + T::one();
+        }
+        panic!("Invalid order");
+    }
+
+    /// Get generator Point<T>
+    #[inline]
+    fn get_gp(&self) -> Point<T> {
+        self.p_g.clone()
+    }
+
+    /// Get prime p
+    #[inline]
+    fn get_prime(&self) -> T {
+        self.p.clone()
+    }
+
+    /// Get coefficient a
+    #[inline]
+    fn get_a(&self) -> T {
+        self.a.clone()
+    }
+
+    /// Get order n
+    #[inline]
+    fn get_order(&self) -> T {
+        self.n.clone()
+    }
+
+    /// Get cofactor h
+    #[inline]
+    fn get_cofac(&self) -> T {
+        self.h.clone()
+    }
+
+    /// EC2OSP for Edwards curves:  RFC 8032 encoding whatever the format (see encode_ed)
+    fn encode_point(&self, p: &Point<T>, _format: PointFormat) -> Vec<u8> {
+        self.encode_ed(p)
+    }
+
+    fn print(&self, s: &str, p: &Point<T>) {
+        println!("{}: [{}, {}, {}]", s, p.x, p.y, p.z);
+    }
+}
+
+impl<T: Number> EcpE<T> {
+    /// Get coefficient d
+    #[inline]
+    pub fn get_d(&self) -> T {
+        self.d.clone()
+    }
+
+    /// Encoding length of RFC 8032:  b / 8 octets,  b = bitlen(p) + 1
+    pub fn get_encoded_len(&self) -> usize {
+        self.p.bit_len() / 8 + 1
+    }
+
+    /// Point encoding of RFC 8032 (5.1.2 / 5.2.2)
+    /// - y in little-endian,  the least significant bit of x in the top bit of the last octet
+    pub fn encode_ed(&self, p_p: &Point<T>) -> Vec<u8> {
+        let q = self.to_affine(p_p);
+        let len = self.get_encoded_len();
+        let mut enc = q.y.to_bytes_be_pad(len);
+        enc.reverse();
+        enc[len - 1] |= u8::from(q.x.test_bit(0)) << 7;
+        enc
+    }
+
+    /// Point decoding of RFC 8032 (5.1.3 / 5.2.3)
+    /// - no subgroup check (the cofactored verification of EdDSA does not need it)
+    pub fn decode_ed(&self, enc: &[u8]) -> Result<Point<T>, EcError> {
+        let len = self.get_encoded_len();
+        if enc.len() != len {
+            return Err(EcError::InvalidLength(enc.len()));
+        }
+        let xt = enc[len - 1] >> 7;
+        let mut yb = enc.to_vec();
+        yb[len - 1] &= 0x7f;
+        yb.reverse();
+        let y = T::from_bytes_be(&yb);
+        if y >= self.p {
+            return Err(EcError::CoordinateOutOfRange);
+        }
+        let p_p = self
+            .point_from_y(&y, xt.into())
+            .ok_or(EcError::NotQuadraticResidue)?;
+        if p_p.x.is_zero() && xt == 1 {
+            return Err(EcError::NotOnCurve); // -0
+        }
+        Ok(p_p)
+    }
+
+    /// Find points on curve at y
+    /// - returns: (x, y, 1) with x = xt mod 2,  None if there is no point at y or y is not in [0, p)
+    pub fn point_from_y(&self, y: &T, xt: u32) -> Option<Point<T>> {
+        if y < &T::zero() || y >= &self.p {
+            return None;
+        }
+        let f = &self.field;
+
+        // x^2 = (y^2 - 1) / (d * y^2 - a)
+        let y = f.elem(y);
+        let y2 = y.square();
+        let u = &y2 - &f.one();
+        let v = &(&f.elem(&self.d) * &y2) - &f.elem(&self.a);
+        let mut x = (&u * &v.inv()?).sqrt()?;
+        if x.is_odd() != (xt & 1 == 1) {
+            x = -x;
+        }
+        Some(self.point_from_xy(&x.value(), &y.value()))
+    }
+
+    /// Coordinates as field elements
+    fn elems(
+        &self,
+        p_p: &Point<T>,
+    ) -> (
+        FieldElement<'_, T>,
+        FieldElement<'_, T>,
+        FieldElement<'_, T>,
+    ) {
+        let f = &self.field;
+        (f.elem(&p_p.x), f.elem(&p_p.y), f.elem(&p_p.z))
+    }
+
+    /// Coordinates of a point in the field representation
+    fn elems_m<'a>(
+        &'a self,
+        p_p: &'a ExtPoint<T>,
+    ) -> (
+        FieldElement<'a, T>,
+        FieldElement<'a, T>,
+        FieldElement<'a, T>,
+        FieldElement<'a, T>,
+    ) {
+        let f = &self.field;
+        (
+            f.elem_ref(&p_p.x),
+            f.elem_ref(&p_p.y),
+            f.elem_ref(&p_p.z),
+            f.elem_ref(&p_p.t),
+        )
+    }
+
+    /// Zero point (0 : 1 : 1 : 0) in the field representation
+    fn zero_m(&self) -> ExtPoint<T> {
+        let f = &self.field;
+        ExtPoint {
+            x: T::zero(),
+            y: f.one().into_repr(),
+            z: f.one().into_repr(),
+            t: T::zero(),
+        }
+    }
+
+    /// Point to extended coordinates in the field representation
+    /// - (X : Y : 1) -> (X : Y : 1 : XY),  otherwise (XZ : YZ : Z^2 : XY)
+    fn to_field(&self, p_p: &Point<T>) -> ExtPoint<T> {
+        let (x, y, z) = self.elems(p_p);
+        let t = (&x * &y).into_repr();
+        if p_p.z.is_one() {
+            return ExtPoint {
+                x: x.into_repr(),
+                y: y.into_repr(),
+                z: z.into_repr(),
+                t,
+            };
+        }
+        ExtPoint {
+            x: (&x * &z).into_repr(),
+            y: (&y * &z).into_repr(),
+            z: z.square().into_repr(),
+            t,
+        }
+    }
+
+    /// Point from the field representation (T dropped)
+    fn to_normal(&self, p_p: &ExtPoint<T>) -> Point<T> {
+        let (x, y, z, _) = self.elems_m(p_p);
+        Point {
+            axis: EcAxis::Edwards,
+            x: x.value(),
+            y: y.value(),
+            z: z.value(),
+        }
+    }
+
+    /// Negate P  (field representation)
+    fn negate_m(&self, p_p: &ExtPoint<T>) -> ExtPoint<T> {
+        let f = &self.field;
+        ExtPoint {
+            x: (-f.elem_ref(&p_p.x)).into_repr(),
+            y: p_p.y.clone(),
+            z: p_p.z.clone(),
+            t: (-f.elem_ref(&p_p.t)).into_repr(),
+        }
+    }
+
+    /// a * v  (field representation)
+    fn mul_a<'a>(&'a self, v: &FieldElement<'a, T>) -> FieldElement<'a, T> {
+        if self.a_m1 {
+            -v
+        } else {
+            &self.field.elem_ref(&self.a_m) * v
+        }
+    }
+
+    /// EC point double : Q = 2 * P  (field representation, dbl-2008-hwcd)
+    fn double_m(&self, p_p: &ExtPoint<T>) -> ExtPoint<T> {
+        let (x, y, z, _) = self.elems_m(p_p);
+
+        let a = x.square(); // A = X^2
+        let b = y.square(); // B = Y^2
+        let c = z.square().double(); // C = 2 * Z^2
+        let d = self.mul_a(&a); // D = a * A
+        let e = &(&(&x + &y).square() - &a) - &b; // E = (X + Y)^2 - A - B
+        let g = &d + &b; // G = D + B
+        let f = &g - &c; // F = G - C
+        let h = &d - &b; // H = D - B
+
+        ExtPoint {
+            x: (&e * &f).into_repr(),
+            y: (&g * &h).into_repr(),
+            z: (&f * &g).into_repr(),
+            t: (&e * &h).into_repr(),
+        }
+    }
+
+    /// EC point add : Q = P1 + P2  (field representation, add-2008-hwcd)
+    /// - complete:  no special case for P1 = P2 or the zero point
+    fn add_m(&self, p_p1: &ExtPoint<T>, p_p2: &ExtPoint<T>) -> ExtPoint<T> {
+        let (x1, y1, z1, t1) = self.elems_m(p_p1);
+        let (x2, y2, z2, t2) = self.elems_m(p_p2);
+
+        let a = &x1 * &x2; // A = X1 * X2
+        let b = &y1 * &y2; // B = Y1 * Y2
+        let c = &(&t1 * &self.field.elem_ref(&self.d_m)) * &t2; // C = T1 * d * T2
+        let d = &z1 * &z2; // D = Z1 * Z2
+        let e = &(&(&(&x1 + &y1) * &(&x2 + &y2)) - &a) - &b; // E = (X1 + Y1) * (X2 + Y2) - A - B
+        let f = &d - &c; // F = D - C
+        let g = &d + &c; // G = D + C
+        let h = &b - &self.mul_a(&a); // H = B - a * A
+
+        ExtPoint {
+            x: (&e * &f).into_repr(),
+            y: (&g * &h).into_repr(),
+            z: (&f * &g).into_repr(),
+            t: (&e * &h).into_repr(),
+        }
+    }
+
+    /// Curve by id ("edwards25519", "edwards448", "Ed25519", "Ed448")
+    /// - panics if the curve is unknown (see try_new)
+    pub fn new(ec_name: &str) -> EcpE<T> {
+        Self::try_new(ec_name).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Curve by id,  Err if the curve is unknown or its field does not fit T
+    /// - uses the special-form reduction of the curve prime if there is one
+    pub fn try_new(ec_name: &str) -> Result<EcpE<T>, UnknownCurve> {
+        let ec = EdParam::try_new(ec_name)?;
+        let p: T = ec.get_prime();
+        check_field(ec_name, &p)?;
+        let field = PrimeField::for_prime(&p);
+        let a = field.elem(&ec.get_a());
+        let d = field.elem(&ec.get_d());
+        let (gx, gy) = (ec.get_gx(), ec.get_gy());
+
+        Ok(EcpE {
+            id: ec.id,
+            a_m1: (&a + &field.one()).is_zero(),
+            a_m: a.into_repr(),
+            d_m: d.into_repr(),
+            base: OnceLock::new(),
+            a: ec.get_a(),
+            d: ec.get_d(),
+            n: ec.get_order(),
+            h: ec.get_cofactor(),
+            p_zero: Point {
+                axis: EcAxis::Edwards,
+                x: T::zero(),
+                y: T::one(),
+                z: T::one(),
+            },
+            p_g: Point {
+                axis: EcAxis::Edwards,
+                x: gx,
+                y: gy,
+                z: T::one(),
+            },
+            p,
+            field,
+        })
+    }
+}
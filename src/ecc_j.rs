@@ -3,9 +3,15 @@
 //!
 //  Written by blanclux
 //  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
-use super::ec_param::{EcParam, ParamOp};
-use super::number::Number;
-use super::{EcAxis, EcOp, Point};
+use std::sync::OnceLock;
+
+use super::ec_param::{check_field, EcParam, ParamOp, UnknownCurve};
+use super::field::{FieldElement, PrimeField};
+use super::number::{fits_field, Number};
+use super::{
+    fixed_base_table, get_wnaf_size, mul_fixed_base, mul_ladder, mul_multi_wnaf, mul_window,
+    mul_wnaf, odd_multiples, BaseTable, EcAxis, EcOp, Point, WeierstrassOp,
+};
 
 /// Elliptic curve  (Projective - Jacobian)
 #[derive(Clone)]
@@ -17,6 +23,14 @@ pub struct EcpJ<T: Number> {
     pub h: T,
     pub p_zero: Point<T>,
     pub p_g: Point<T>,
+    /// GF(p) (Montgomery form or special-form reduction)
+    field: PrimeField<T>,
+    /// a in the field representation
+    a_m: T,
+    /// a = -3 mod p
+    a_m3: bool,
+    /// Fixed-base table of p_g (built on first mul_base)
+    base: OnceLock<BaseTable<Point<T>>>,
 }
 
 impl<T: Number> EcOp<T> for EcpJ<T> {
@@ -41,98 +55,52 @@ impl<T: Number> EcOp<T> for EcpJ<T> {
     }
 
     /// Point P is on curve ?
-    #[allow(clippy::many_single_char_names)]
     fn on_curve(&self, p_p: &Point<T>) -> bool {
-        let (l, r);
-        let (z2, z4, z6);
-
-        if p_p.z.is_one() {
+        if p_p.is_zero() {
             return true;
         }
-        // Y^2 = X^3 + aXZ^2 + bZ^6
-        l = p_p.y.mul_ref(&p_p.y) % &self.p;
-        z2 = p_p.z.mul_ref(&p_p.z) % &self.p;
-        z4 = z2.mul_ref(&z2) % &self.p;
-        z6 = z2.mul_ref(&z4) % &self.p;
-        r = ((p_p.x.mul_ref(&p_p.x) * &p_p.x) + self.a.mul_ref(&p_p.x) * &z4 + self.b.mul_ref(&z6))
-            % &self.p;
-
-        l == r
+        let f = &self.field;
+        let (x, y, z) = self.elems(p_p);
+
+        // Y^2 = X^3 + aXZ^4 + bZ^6
+        let z2 = z.square();
+        let z4 = z2.square();
+        let r = &(&x.square() * &x) + &(&(&f.elem(&self.a) * &x) * &z4);
+        y.square() == &r + &(&f.elem(&self.b) * &(&z4 * &z2))
     }
 
     /// Convert 3D to 2D
     fn to_affine(&self, p_p: &Point<T>) -> Point<T> {
-        if p_p.z.is_one() {
-            return p_p.clone();
-        }
-
-        // x = x * z^(-2)
-        let x = (Number::mod_inv(&(p_p.z.mul_ref(&p_p.z)), &self.p) * &p_p.x) % &self.p;
-        // y = y * z^(-3)
-        let y = (Number::mod_inv(&Number::mod_pow(&p_p.z, &T::from(3u32), &self.p), &self.p)
-            * &p_p.y)
-            % &self.p;
-
-        Point {
-            axis: EcAxis::Proj,
-            x,
-            y,
-            z: T::one(),
-        }
+        let mut p_r = p_p.clone();
+        self.normalize(&mut p_r);
+        p_r
     }
 
     fn normalize(&self, p_p: &mut Point<T>) {
         if p_p.z.is_one() || p_p.z.is_zero() {
             return;
         }
+        let (x, y, z) = self.elems(p_p);
 
-        // x = x * z^(-2)
-        p_p.x = (Number::mod_inv(&(p_p.z.mul_ref(&p_p.z)), &self.p) * &p_p.x) % &self.p;
-        // y = y * z^(-3)
-        p_p.y = (Number::mod_inv(&Number::mod_pow(&p_p.z, &T::from(3u32), &self.p), &self.p)
-            * &p_p.y)
-            % &self.p;
+        // x = x * z^(-2),  y = y * z^(-3)
+        let zi = z.inv().unwrap();
+        let zi2 = zi.square();
+        p_p.x = (&x * &zi2).value();
+        p_p.y = (&(&y * &zi2) * &zi).value();
         p_p.z = T::one();
     }
 
     /// Check equality
     fn equals(&self, p1: &Point<T>, p2: &Point<T>) -> bool {
-        let mut result = true;
-        let (mut z1, mut z2);
-
-        if p1.z.is_one() && p2.z.is_one() {
-            return p1.x == p2.x && p1.y == p2.y;
-        }
-        if p1.z.is_one() {
-            // z2^2
-            z2 = p2.z.mul_ref(&p2.z) % &self.p;
-            // x1 * z2^2 = x2 ?
-            result = result && (p2.x == p1.x.mul_ref(&z2) % &self.p);
-            z2 = z2.mul_ref(&p1.z) % &self.p;
-            // y1 * z2^3 = y1 ?
-            result = result && (p1.y == p1.y.mul_ref(&z2) % &self.p);
-            return result;
+        if p1.is_zero() || p2.is_zero() {
+            return p1.is_zero() && p2.is_zero();
         }
-        z1 = p1.z.mul_ref(&p1.z) % &self.p; // z1^2
-        if p2.z.is_one() {
-            // P1.x * z^2 = x ?
-            result = result && (p1.x == p2.x.mul_ref(&z1) % &self.p);
-            z1 = z1.mul_ref(&p1.z);
-            // P1.y * z^3 = y ?
-            result = result && (p1.y == p2.y.mul_ref(&z1) % &self.p);
-        } else {
-            // z2^2
-            z2 = p2.z.mul_ref(&p2.z) % &self.p;
-            // x1 * z2^2 = x2 * z1^2 ?
-            result = result && (p1.x.mul_ref(&z2) % &self.p == p2.x.mul_ref(&z1) % &self.p);
-            // z1^3
-            z1 = p1.z.mul_ref(&z1) % &self.p;
-            // z2^3
-            z2 = p2.z.mul_ref(&z2) % &self.p;
-            // y1 * z2^3 = y2 * z1^3 ?
-            result = result && (p1.y.mul_ref(&z2) % &self.p == p2.y.mul_ref(&z1) % &self.p);
-        }
-        result
+        let (x1, y1, z1) = self.elems(p1);
+        let (x2, y2, z2) = self.elems(p2);
+
+        // x1 * z2^2 = x2 * z1^2,  y1 * z2^3 = y2 * z1^3 ?
+        let (z1s, z2s) = (z1.square(), z2.square());
+        &x1 * &z2s == &x2 * &z1s && &y1 * &(&z2s * &z2) == &y2 * &(&z1s * &z1)
     }
 
     /// Negate P
@@ -140,181 +108,160 @@ impl<T: Number> EcOp<T> for EcpJ<T> {
         Point {
             axis: EcAxis::Proj,
             x: p_p.x.clone(),
-            y: self.p.clone() - p_p.y.clone(),
+            y: (-self.field.elem(&p_p.y)).value(),
             z: p_p.z.clone(),
         }
     }
 
     /// EC point double : Q = 2 * P
     fn double(&self, p_p: &Point<T>) -> Point<T> {
-        let (mut t1, mut t2, mut t3) = (p_p.x.clone(), p_p.y.clone(), p_p.z.clone());
-        let (mut t4, mut t5);
-
-        if t2.is_zero() || t3.is_zero() {
-            return Point {
-                axis: EcAxis::Proj,
-                x: T::one(),
-                y: T::one(),
-                z: T::zero(),
-            };
-        }
-        if self.a == self.p.sub_ref(&T::from(3u32)) % &self.p {
-            t4 = t3.mul_ref(&t3) % &self.p;
-            t5 = t1.sub_ref(&t4) % &self.p;
-            t4 = t1.add_ref(&t4) % &self.p;
-            t5 = t4.mul_ref(&t5) % &self.p;
-            t4 = T::from(3u32) * &t5 % &self.p;
-        } else {
-            t4 = self.a.clone();
-            t5 = t3.mul_ref(&t3) % &self.p;
-            t5 = t5.mul_ref(&t5) % &self.p;
-            t5 = t4.mul_ref(&t5) % &self.p;
-            t4 = t1.mul_ref(&t1) % &self.p;
-            t4 = T::from(3u32) * &t4 % &self.p;
-            t4 = t4.add_ref(&t5) % &self.p;
-        }
-        t3 = t2.mul_ref(&t3) % &self.p;
-        t3 = Number::mod_cal(&(T::from(2u32) * &t3), &self.p);
-        t2 = t2.mul_ref(&t2) % &self.p;
-        t5 = t1.mul_ref(&t2) % &self.p;
-        t5 = T::from(4u32) * &t5 % &self.p;
-        t1 = t4.mul_ref(&t4) % &self.p;
-        t1 = Number::mod_cal(&(t1.sub_ref(&(T::from(2u32) * &t5))), &self.p);
-        t2 = t2.mul_ref(&t2) % &self.p;
-        t2 = T::from(8u32) * &t2 % &self.p;
-        t5 = t5.sub_ref(&t1) % &self.p;
-        t5 = t4.mul_ref(&t5) % &self.p;
-        t2 = Number::mod_cal(&(t5.sub_ref(&t2)), &self.p);
-
-        Point {
-            axis: EcAxis::Proj,
-            x: t1,
-            y: t2,
-            z: t3,
-        }
+        self.to_normal(&self.double_m(&self.to_field(p_p)))
     }
 
     /// EC Point<T> add : Q = P1 + P2
     fn add(&self, p_p1: &Point<T>, p_p2: &Point<T>) -> Point<T> {
-        if p_p1.z.is_zero() {
-            return p_p2.clone();
-        }
-        if p_p2.z.is_zero() {
-            return p_p1.clone();
-        }
-        if self.equals(p_p1, p_p2) {
-            return self.double(p_p1);
-        }
+        self.to_normal(&self.add_m(&self.to_field(p_p1), &self.to_field(p_p2)))
+    }
 
-        let (mut t1, mut t2, mut t3) = (p_p1.x.clone(), p_p1.y.clone(), p_p1.z.clone());
-        let (mut t4, mut t5) = (p_p2.x.clone(), p_p2.y.clone());
-        let (mut t6, mut t7): (T, T);
-
-        t6 = T::one();
-        if !p_p2.z.is_one() {
-            t6 = p_p2.z.clone() % &self.p;
-            t7 = t6.mul_ref(&t6) % &self.p;
-            t1 = t1.mul_ref(&t7) % &self.p; // U0 = P1.x * P2.z^2
-            t7 = t6.mul_ref(&t7) % &self.p;
-            t2 = t2.mul_ref(&t7) % &self.p; // S0 = P1.y * P2.z^3
-        }
+    /// Multiply (k * P)
+    /// Sliding window multiplication (in the field representation)
+    fn mul(&self, p_p: &Point<T>, k: &T) -> Point<T> {
+        let p_r = mul_window(
+            &self.to_field(p_p),
+            k,
+            self.get_zero(),
+            |p1, p2| self.add_m(p1, p2),
+            |p| self.double_m(p),
+        );
+        self.to_normal(&p_r)
+    }
 
-        t7 = t3.mul_ref(&t3) % &self.p;
-        t4 = t4.mul_ref(&t7) % &self.p; // U1 = P2.x * P1.z^2
-        t7 = t3.mul_ref(&t7) % &self.p;
-        t5 = t5.mul_ref(&t7) % &self.p; // S1 = P2.y * P1.z^3
-        t4 = t1.sub_ref(&t4) % &self.p; // W = U0 - U1
-        t5 = t2.sub_ref(&t5) % &self.p; // R = S0 - S1
+    /// Multiply (k * P) (wNAF, in the field representation)
+    fn mul_wnaf(&self, p_p: &Point<T>, k: &T) -> Point<T> {
+        let w = get_wnaf_size(k);
+        let table = odd_multiples(
+            &self.to_field(p_p),
+            w,
+            |p1, p2| self.add_m(p1, p2),
+            |p| self.double_m(p),
+        );
+        let p_r = mul_wnaf(
+            &table,
+            k,
+            w,
+            self.get_zero(),
+            |p1, p2| self.add_m(p1, p2),
+            |p| self.double_m(p),
+            |p| self.negate_m(p),
+        );
+        self.to_normal(&p_r)
+    }
 
-        if t4.is_zero() {
-            if t5.is_zero() {
-                return Point {
-                    axis: EcAxis::Proj,
-                    x: T::zero(),
-                    y: T::zero(),
-                    z: T::zero(),
-                };
-            } else {
-                return Point {
-                    axis: EcAxis::Proj,
-                    x: T::one(),
-                    y: T::one(),
-                    z: T::zero(),
-                };
-            }
-        }
-        t1 = (T::from(2u32) * &t1 - &t4) % &self.p; // T = U0 + U1
-        t2 = (T::from(2u32) * &t2 - &t5) % &self.p; // M = S0 + S1
-        if !p_p2.z.is_one() {
-            t3 = t3.mul_ref(&t6) % &self.p;
-        }
+    /// Multiply and add (k * P + l * Q) (interleaved wNAF, in the field representation)
+    fn mul2(&self, p_p: &Point<T>, k: &T, p_q: &Point<T>, l: &T) -> Point<T> {
+        let p_r = mul_multi_wnaf(
+            &[(&self.to_field(p_p), k), (&self.to_field(p_q), l)],
+            self.get_zero(),
+            |p1, p2| self.add_m(p1, p2),
+            |p| self.double_m(p),
+            |p| self.negate_m(p),
+        );
+        self.to_normal(&p_r)
+    }
 
-        t3 = Number::mod_cal(&(t3.mul_ref(&t4)), &self.p); // Z3 = z1*z2*W
-        t7 = t4.mul_ref(&t4) % &self.p;
-        t4 = t4.mul_ref(&t7) % &self.p;
-        t7 = t1.mul_ref(&t7) % &self.p;
-        t1 = t5.mul_ref(&t5) % &self.p;
-        t1 = Number::mod_cal(&(t1.sub_ref(&t7)), &self.p); // X3 = R^2 - T*W^2
+    /// Multi-scalar multiplication (interleaved wNAF, in the field representation)
+    fn mul_multi(&self, terms: &[(&Point<T>, &T)]) -> Point<T> {
+        let points: Vec<Point<T>> = terms.iter().map(|(p, _)| self.to_field(p)).collect();
+        let terms: Vec<(&Point<T>, &T)> = points.iter().zip(terms).map(|(p, t)| (p, t.1)).collect();
+        let p_r = mul_multi_wnaf(
+            &terms,
+            self.get_zero(),
+            |p1, p2| self.add_m(p1, p2),
+            |p| self.double_m(p),
+            |p| self.negate_m(p),
+        );
+        self.to_normal(&p_r)
+    }
 
-        t7 = (t7.sub_ref(&(T::from(2u32) * &t1))) % &self.p; // V = T*W^2 - 2*x3
-        t5 = t5.mul_ref(&t7) % &self.p;
-        t4 = t2.mul_ref(&t4) % &self.p;
-        t2 = t5.sub_ref(&t4) % &self.p;
-        t2 = Number::mod_inv(&T::from(2u32), &self.p) * &t2 % &self.p; // Y3 = (V*R - M*W^3)/2
-        t2 = Number::mod_cal(&t2, &self.p);
+    /// Multiply (k * G) (fixed-base table, in the field representation)
+    fn mul_base(&self, k: &T) -> Point<T> {
+        if !self.n.test_bit(0) {
+            return self.mul_ct(&self.p_g, k);
+        }
+        let p_r = mul_fixed_base(
+            self.get_base_table(),
+            &Number::mod_cal(k, &self.n),
+            &self.n,
+            |p1, p2| self.add_m(p1, p2),
+            |p| self.negate_m(p),
+        );
+        self.to_normal(&p_r)
+    }
 
-        Point {
-            axis: EcAxis::Proj,
-            x: t1,
-            y: t2,
-            z: t3,
+    /// Multiply (k * P) (Binary method, in the field representation)
+    fn mul_bin(&self, p_p: &Point<T>, k: &T) -> Point<T> {
+        let mut p_r = self.get_zero();
+        let mut p_s = self.to_field(p_p);
+
+        for s in 0..k.bit_len() {
+            if k.test_bit(s) {
+                p_r = self.add_m(&p_r, &p_s);
+            }
+            p_s = self.double_m(&p_s);
         }
+        self.to_normal(&p_r)
+    }
+
+    /// Multiply (k * P) (Montgomery ladder, in the field representation)
+    fn mul_ct(&self, p_p: &Point<T>, k: &T) -> Point<T> {
+        let bits = self.n.bit_len().max(k.bit_len());
+        let p_r = mul_ladder(
+            &self.to_field(p_p),
+            k,
+            bits,
+            self.get_zero(),
+            |p1, p2| self.add_m(p1, p2),
+            |p| self.double_m(p),
+        );
+        self.to_normal(&p_r)
     }
 
     /// Generate a random point
     fn gen_point(&self) -> Point<T> {
-        let (mut x, mut y, mut y2): (T, T, T);
-        let p = self.p.clone();
+        let f = &self.field;
+        let p1 = self.p.sub_ref(&T::one());
 
         loop {
-            x = Number::gen_rand(&T::from(1u32), &(p.sub_ref(&T::from(1u32))));
-
-            // y^2 = x^3 + ax + b = (x^2 + a) * x + b
-            y2 = Number::mod_cal(&((x.mul_ref(&x) + &self.a) * &x + &self.b), &self.p);
-            y = Number::mod_sqrt(&y2, &self.p);
-
-            let pt = Point {
-                axis: EcAxis::Affine,
-                x,
-                y,
-                z: T::one(),
-            };
-            if self.on_curve(&pt) {
-                return pt;
+            let x = Number::gen_rand(&T::one(), &p1);
+            if let Some(y) = self.rhs(&f.elem(&x)).sqrt() {
+                return Point {
+                    axis: EcAxis::Affine,
+                    x,
+                    y: y.value(),
+                    z: T::one(),
+                };
             }
         }
     }
 
     /// Find points on curve at x
-    /// - returns: ((x, y, 1), (x, -y, 1)) or not found exception
-    fn point_from_x(&self, x: &T, yt: u32) -> Point<T> {
-        assert!(x < &self.p);
-
-        // y^2 = x^3 + ax + b = (x^2 + a) * x + b
-        let y2 = Number::mod_cal(&((x.mul_ref(x) + &self.a) * x + &self.b), &self.p);
-        let mut y = Number::mod_sqrt(&y2, &self.p);
+    /// - returns: (x, y, 1) with y = yt mod 2,  None if there is no point at x or x is not in [0, p)
+    fn point_from_x(&self, x: &T, yt: u32) -> Option<Point<T>> {
+        if x < &T::zero() || x >= &self.p {
+            return None;
+        }
 
-        let bit0 = y.clone() % T::from(2u32);
-        if bit0 != T::from(yt) {
-            y = self.p.sub_ref(&y);
+        let mut y = self.rhs(&self.field.elem(x)).sqrt()?;
+        if y.is_odd() != (yt & 1 == 1) {
+            y = -y;
         }
 
-        Point {
+        Some(Point {
             axis: EcAxis::Proj,
             x: x.clone(),
-            y,
+            y: y.value(),
             z: T::one(),
-        }
+        })
     }
 
     /// Find points on curve at (x, y)
@@ -327,66 +274,6 @@ impl<T: Number> EcOp<T> for EcpJ<T> {
         }
     }
 
-    /// OS2ECPP: Decode a point on this curve which has been encoded using point
-    /// compression(X9.62) returning the point.
-    #[allow(unused_assignments)]
-    fn decode_point(&self, enc: &[u8]) -> Point<T> {
-        if enc.len() == 1 {
-            if enc[0] == 0 {
-                return self.get_zero();
-            } else {
-                panic!("Invalid point compression");
-            }
-        }
-
-        let mut x = T::zero();
-        let mut y = T::zero();
-
-        match enc[0] {
-            // compressed
-            0x02 | 0x03 => {
-                let ln = enc.len() - 1;
-                let xb = &enc[1..ln + 1];
-                let x = T::from_bytes_be(xb);
-                let yt = enc[0] & 1;
-                return self.point_from_x(&x, yt.into());
-            }
-            // uncompressed
-            0x04 => {
-                let ln = (enc.len() - 1) / 2;
-                let xb = &enc[1..(ln + 1)];
-                let yb = &enc[(ln + 1)..enc.len()];
-                x = T::from_bytes_be(xb);
-                y = T::from_bytes_be(yb);
-            }
-            _ => panic!("Invalid point compression"),
-        }
-        Point {
-            axis: EcAxis::Proj,
-            x,
-            y,
-            z: T::one(),
-        }
-    }
-
-    /// Returns the field element encoded with point compression.
-    fn get_encoded(&self, p: &Point<T>) -> Vec<u8> {
-        let q = self.to_affine(p);
-
-        let y = q.y;
-        let x = q.x;
-        // y~ = y mod 2
-        let yt = y % T::from(2u32);
-        let hd = if yt == T::one() { 0x03 } else { 0x02 };
-
-        let xb = x.to_bytes_be();
-        let mut po = vec![0u8; xb.len() + 1];
-        po[0] = hd;
-        po[1..].copy_from_slice(&xb[..xb.len()]);
-
-        po
-    }
-
     /// Order of point P
     fn calc_order(&self, p_p: &Point<T>) -> T {
         let mut m = T::one();
@@ -406,6 +293,18 @@ impl<T: Number> EcOp<T> for EcpJ<T> {
         self.p_g.clone()
     }
 
+    /// Get prime p
+    #[inline]
+    fn get_prime(&self) -> T {
+        self.p.clone()
+    }
+
+    /// Get coefficient a
+    #[inline]
+    fn get_a(&self) -> T {
+        self.a.clone()
+    }
+
     /// Get order
     #[inline]
     fn get_order(&self) -> T {
@@ -424,11 +323,249 @@ impl<T: Number> EcOp<T> for EcpJ<T> {
     }
 }
 
+impl<T: Number> WeierstrassOp<T> for EcpJ<T> {
+    /// Get coefficient b
+    #[inline]
+    fn get_b(&self) -> T {
+        self.b.clone()
+    }
+}
+
 impl<T: Number> EcpJ<T> {
+    /// Coordinates as field elements
+    fn elems(
+        &self,
+        p_p: &Point<T>,
+    ) -> (
+        FieldElement<'_, T>,
+        FieldElement<'_, T>,
+        FieldElement<'_, T>,
+    ) {
+        let f = &self.field;
+        (f.elem(&p_p.x), f.elem(&p_p.y), f.elem(&p_p.z))
+    }
+
+    /// Coordinates of a point in the field representation
+    fn elems_m<'a>(
+        &'a self,
+        p_p: &'a Point<T>,
+    ) -> (
+        FieldElement<'a, T>,
+        FieldElement<'a, T>,
+        FieldElement<'a, T>,
+    ) {
+        let f = &self.field;
+        (f.elem_ref(&p_p.x), f.elem_ref(&p_p.y), f.elem_ref(&p_p.z))
+    }
+
+    /// x^3 + ax + b = (x^2 + a) * x + b
+    fn rhs<'a>(&'a self, x: &FieldElement<'a, T>) -> FieldElement<'a, T> {
+        let f = &self.field;
+        &(&(&x.square() + &f.elem(&self.a)) * x) + &f.elem(&self.b)
+    }
+
+    /// Point to the field representation
+    fn to_field(&self, p_p: &Point<T>) -> Point<T> {
+        if self.is_zero(p_p) {
+            return self.get_zero();
+        }
+        let (x, y, z) = self.elems(p_p);
+        Point {
+            axis: EcAxis::Proj,
+            x: x.into_repr(),
+            y: y.into_repr(),
+            z: z.into_repr(),
+        }
+    }
+
+    /// Point from the field representation
+    fn to_normal(&self, p_p: &Point<T>) -> Point<T> {
+        if p_p.z.is_zero() {
+            return self.get_zero();
+        }
+        let (x, y, z) = self.elems_m(p_p);
+        Point {
+            axis: EcAxis::Proj,
+            x: x.value(),
+            y: y.value(),
+            z: z.value(),
+        }
+    }
+
+    /// Negate P  (field representation)
+    fn negate_m(&self, p_p: &Point<T>) -> Point<T> {
+        Point {
+            axis: EcAxis::Proj,
+            x: p_p.x.clone(),
+            y: (-self.field.elem_ref(&p_p.y)).into_repr(),
+            z: p_p.z.clone(),
+        }
+    }
+
+    /// Fixed-base table of G  (affine points in the field representation)
+    fn get_base_table(&self) -> &BaseTable<Point<T>> {
+        self.base.get_or_init(|| {
+            let mut table = fixed_base_table(
+                &self.to_field(&self.p_g),
+                self.n.bit_len(),
+                |p1, p2| self.add_m(p1, p2),
+                |p| self.double_m(p),
+            );
+            // z = 1 for the mixed additions of add_m
+            let mut pts: Vec<&mut Point<T>> = table.iter_mut().flatten().collect();
+            self.normalize_batch_m(&mut pts);
+            table
+        })
+    }
+
+    /// Normalize points to z = 1 with a single inversion  (field representation)
+    /// - z_i^(-1) = (z_0 * .. * z_i)^(-1) * (z_0 * .. * z_(i-1)),  no point at infinity
+    fn normalize_batch_m(&self, pts: &mut [&mut Point<T>]) {
+        let f = &self.field;
+
+        // prod[i] = z_0 * .. * z_i
+        let mut prod = Vec::with_capacity(pts.len());
+        let mut acc = f.one().into_repr();
+        for p in pts.iter() {
+            acc = (f.elem_ref(&acc) * f.elem_ref(&p.z)).into_repr();
+            prod.push(acc.clone());
+        }
+        let mut inv = f.elem_ref(&acc).inv().unwrap().into_repr();
+        for i in (0..pts.len()).rev() {
+            let p = &mut pts[i];
+            let next = (f.elem_ref(&inv) * f.elem_ref(&p.z)).into_repr();
+            let zi = match i {
+                0 => f.elem_ref(&inv),
+                _ => f.elem_ref(&inv) * f.elem_ref(&prod[i - 1]),
+            };
+            let zi2 = zi.square();
+            let x = f.elem_ref(&p.x) * &zi2;
+            let y = f.elem_ref(&p.y) * &zi2 * &zi;
+            p.x = x.into_repr();
+            p.y = y.into_repr();
+            p.z = f.one().into_repr();
+            inv = next;
+        }
+    }
+
+    /// EC point double : Q = 2 * P  (field representation)
+    fn double_m(&self, p_p: &Point<T>) -> Point<T> {
+        let f = &self.field;
+        let (x, y, z) = self.elems_m(p_p);
+
+        if y.is_zero() || z.is_zero() {
+            return self.get_zero();
+        }
+        let m = if self.a_m3 {
+            // 3 * (x - z^2) * (x + z^2)
+            let z2 = z.square();
+            let t = (&x - &z2) * (&x + &z2);
+            &t.double() + &t
+        } else {
+            // 3 * x^2 + a * z^4
+            let x2 = x.square();
+            &(&x2.double() + &x2) + &(&f.elem_ref(&self.a_m) * &z.square().square())
+        };
+        let z3 = (&y * &z).double();
+        let y2 = y.square();
+        let s = (&x * &y2).double().double(); // S = 4 * x * y^2
+        let x3 = &m.square() - &s.double();
+        let y4 = y2.square().double().double().double(); // 8 * y^4
+        let y3 = &(&m * &(&s - &x3)) - &y4;
+
+        Point {
+            axis: EcAxis::Proj,
+            x: x3.into_repr(),
+            y: y3.into_repr(),
+            z: z3.into_repr(),
+        }
+    }
+
+    /// EC point add : Q = P1 + P2  (field representation)
+    fn add_m(&self, p_p1: &Point<T>, p_p2: &Point<T>) -> Point<T> {
+        if p_p1.z.is_zero() {
+            return p_p2.clone();
+        }
+        if p_p2.z.is_zero() {
+            return p_p1.clone();
+        }
+        let (x1, y1, z1) = self.elems_m(p_p1);
+        let (x2, y2, z2) = self.elems_m(p_p2);
+
+        let z2_one = z2.is_one();
+        let (u0, s0) = if z2_one {
+            (x1, y1)
+        } else {
+            let zz = z2.square();
+            (&x1 * &zz, &y1 * &(&zz * &z2)) // U0 = x1 * z2^2,  S0 = y1 * z2^3
+        };
+        let zz = z1.square();
+        let u1 = &x2 * &zz; // U1 = x2 * z1^2
+        let s1 = &y2 * &(&zz * &z1); // S1 = y2 * z1^3
+        let w = &u0 - &u1; // W = U0 - U1
+        let r = &s0 - &s1; // R = S0 - S1
+
+        if w.is_zero() {
+            if r.is_zero() {
+                return self.double_m(p_p1);
+            }
+            return self.get_zero();
+        }
+        let t = &u0 + &u1; // T = U0 + U1
+        let m = &s0 + &s1; // M = S0 + S1
+
+        let mut z3 = &z1 * &w;
+        if !z2_one {
+            z3 = &z3 * &z2;
+        } // Z3 = z1 * z2 * W
+        let w2 = w.square();
+        let tw2 = &t * &w2;
+        let x3 = &r.square() - &tw2; // X3 = R^2 - T * W^2
+        let v = &tw2 - &x3.double(); // V = T * W^2 - 2 * X3
+        let y3 = (&(&v * &r) - &(&m * &(&w * &w2))).half(); // Y3 = (V * R - M * W^3) / 2
+
+        Point {
+            axis: EcAxis::Proj,
+            x: x3.into_repr(),
+            y: y3.into_repr(),
+            z: z3.into_repr(),
+        }
+    }
+
+    /// Curve by id
+    /// - panics if the curve is unknown (see try_new)
     pub fn new(ec_name: &str) -> EcpJ<T> {
-        let ec = EcParam::new(ec_name);
+        Self::try_new(ec_name).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Curve by id,  Err if the curve is unknown or its field does not fit T
+    /// - uses the special-form reduction of the curve prime if there is one
+    pub fn try_new(ec_name: &str) -> Result<EcpJ<T>, UnknownCurve> {
+        let ec = EcParam::try_new(ec_name)?;
+        check_field(ec_name, &ParamOp::<T>::get_prime(&ec))?;
+        Ok(Self::from_param(&ec))
+    }
+
+    /// Curve from parameters (built-in EcParam or user-supplied CurveParam)
+    /// - uses the special-form reduction of the prime if there is one
+    pub fn from_param<P: ParamOp<T>>(ec: &P) -> EcpJ<T> {
+        Self::with_field(ec, PrimeField::for_prime(&ec.get_prime()))
+    }
+
+    /// Curve from parameters over the given field (e.g. PrimeField::new for Montgomery form)
+    /// - panics if the field does not fit T (see number::fits_field)
+    pub fn with_field<P: ParamOp<T>>(ec: &P, field: PrimeField<T>) -> EcpJ<T> {
+        assert!(
+            fits_field(field.get_prime()),
+            "field too large for the number type"
+        );
+        let a = field.elem(&ec.get_a());
 
         EcpJ {
+            a_m3: (&a + &field.elem(&T::from(3u32))).is_zero(),
+            a_m: a.into_repr(),
+            field,
+            base: OnceLock::new(),
             a: ec.get_a(),
             b: ec.get_b(),
             p: ec.get_prime(),
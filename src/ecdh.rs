@@ -0,0 +1,93 @@
+//!
+//! ECDH (Elliptic Curve Diffie-Hellman key agreement)
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+use std::marker::PhantomData;
+
+use super::number::Number;
+use super::{EcError, EcOp, Point};
+
+/// Peer public key validation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Validation {
+    /// Range, curve equation and subgroup check (SP 800-56A, 5.6.2.3.3)
+    Full,
+    /// Range and curve equation only (SP 800-56A, 5.6.2.3.4)
+    Partial,
+}
+
+/// ECDH on an elliptic curve (any EcOp implementation)
+pub struct Ecdh<'a, T: Number, E: EcOp<T>> {
+    ec: &'a E,
+    cofactor: bool,
+    validation: Validation,
+    _t: PhantomData<T>,
+}
+
+impl<'a, T: Number, E: EcOp<T>> Ecdh<'a, T, E> {
+    /// ECSVDP-DH with full public key validation
+    pub fn new(ec: &'a E) -> Ecdh<'a, T, E> {
+        Ecdh {
+            ec,
+            cofactor: false,
+            validation: Validation::Full,
+            _t: PhantomData,
+        }
+    }
+
+    /// Use cofactor multiplication (ECSVDP-DHC, ECC CDH)
+    pub fn with_cofactor(mut self, cofactor: bool) -> Ecdh<'a, T, E> {
+        self.cofactor = cofactor;
+        self
+    }
+
+    /// Set peer public key validation
+    pub fn with_validation(mut self, validation: Validation) -> Ecdh<'a, T, E> {
+        self.validation = validation;
+        self
+    }
+
+    /// Generate key pair (d, Q = d * G),  1 <= d < n
+    pub fn gen_keypair(&self) -> (T, Point<T>) {
+        let d = Number::gen_rand(&T::one(), &self.ec.get_order());
+        let q = self.ec.to_affine(&self.ec.mul_base(&d));
+        (d, q)
+    }
+
+    /// Shared secret from private key d and encoded peer public key
+    pub fn derive(&self, d: &T, peer: &[u8]) -> Result<Vec<u8>, EcError> {
+        let q = self.ec.try_decode_point_partial(peer)?;
+        self.derive_point(d, &q)
+    }
+
+    /// Shared secret from private key d and peer public key Q
+    /// - returns: x-coordinate of (h) * d * Q, fixed length big-endian
+    pub fn derive_point(&self, d: &T, q: &Point<T>) -> Result<Vec<u8>, EcError> {
+        let n = self.ec.get_order();
+        if d < &T::one() || d >= &n {
+            return Err(EcError::InvalidScalar);
+        }
+        if self.ec.is_zero(q) {
+            return Err(EcError::PointAtInfinity);
+        }
+        match self.validation {
+            Validation::Full => self.ec.check_point(q)?,
+            Validation::Partial => self.ec.check_point_partial(q)?,
+        }
+
+        let k = if self.cofactor {
+            self.ec.get_cofac() * d
+        } else {
+            d.clone()
+        };
+        let p_p = self.ec.mul_ct(q, &k);
+        if self.ec.is_zero(&p_p) {
+            return Err(EcError::PointAtInfinity);
+        }
+
+        // FE2OSP: fixed length field element
+        let ln = self.ec.get_field_len();
+        Ok(self.ec.to_affine(&p_p).x.to_bytes_be_pad(ln))
+    }
+}
@@ -0,0 +1,259 @@
+//!
+//! ECDSA (Elliptic Curve Digital Signature Algorithm)
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+use std::marker::PhantomData;
+
+use hmac::digest::core_api::BlockSizeUser;
+use hmac::digest::Digest;
+
+use super::batch::{verify_equations, Equation};
+use super::number::Number;
+use super::rfc6979::{self, Rfc6979};
+use super::{EcError, EcOp, Point};
+
+/// ECDSA signature (r, s)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Signature<T> {
+    r: T,
+    s: T,
+}
+
+impl<T: Number> Signature<T> {
+    pub fn new(r: T, s: T) -> Signature<T> {
+        Signature { r, s }
+    }
+
+    /// Get r
+    pub fn r(&self) -> &T {
+        &self.r
+    }
+
+    /// Get s
+    pub fn s(&self) -> &T {
+        &self.s
+    }
+}
+
+/// Batch verification item (Q, digest, signature, recovery id)
+pub type BatchItem<'b, T> = (&'b Point<T>, &'b [u8], &'b Signature<T>, u8);
+
+/// ECDSA on an elliptic curve (any EcOp implementation)
+pub struct Ecdsa<'a, T: Number, E: EcOp<T>> {
+    ec: &'a E,
+    _t: PhantomData<T>,
+}
+
+impl<'a, T: Number, E: EcOp<T>> Ecdsa<'a, T, E> {
+    pub fn new(ec: &'a E) -> Ecdsa<'a, T, E> {
+        Ecdsa {
+            ec,
+            _t: PhantomData,
+        }
+    }
+
+    /// Generate key pair (d, Q = d * G),  1 <= d < n
+    pub fn gen_keypair(&self) -> (T, Point<T>) {
+        let d = Number::gen_rand(&T::one(), &self.ec.get_order());
+        let q = self.ec.to_affine(&self.ec.mul_base(&d));
+        (d, q)
+    }
+
+    /// bits2int: leftmost bitlen(n) bits of the hash value
+    pub fn bits2int(&self, h: &[u8]) -> T {
+        rfc6979::bits2int(h, self.ec.get_order().bit_len())
+    }
+
+    /// Sign a hash value with private key d (random nonce)
+    pub fn sign(&self, d: &T, digest: &[u8]) -> Signature<T> {
+        self.sign_recoverable(d, digest).0
+    }
+
+    /// Sign a hash value with private key d (deterministic nonce, RFC 6979)
+    /// - D: hash function used to compute the digest
+    pub fn sign_deterministic<D: Digest + BlockSizeUser>(
+        &self,
+        d: &T,
+        digest: &[u8],
+    ) -> Signature<T> {
+        self.sign_deterministic_recoverable::<D>(d, digest).0
+    }
+
+    /// Sign a hash value with private key d and nonce k
+    /// - returns: None if r = 0 or s = 0 (choose another k)
+    pub fn sign_with_k(&self, d: &T, digest: &[u8], k: &T) -> Option<Signature<T>> {
+        self.sign_with_k_recoverable(d, digest, k)
+            .map(|(sig, _)| sig)
+    }
+
+    /// Sign a hash value with private key d (random nonce)
+    /// - returns: (signature, recovery id)
+    pub fn sign_recoverable(&self, d: &T, digest: &[u8]) -> (Signature<T>, u8) {
+        let n = self.ec.get_order();
+        loop {
+            let k = Number::gen_rand(&T::one(), &n);
+            if let Some(sig) = self.sign_with_k_recoverable(d, digest, &k) {
+                return sig;
+            }
+        }
+    }
+
+    /// Sign a hash value with private key d (deterministic nonce, RFC 6979)
+    /// - returns: (signature, recovery id)
+    pub fn sign_deterministic_recoverable<D: Digest + BlockSizeUser>(
+        &self,
+        d: &T,
+        digest: &[u8],
+    ) -> (Signature<T>, u8) {
+        let mut gen: Rfc6979<T, D> = Rfc6979::new(d, &self.ec.get_order(), digest);
+        loop {
+            let k = gen.next_k();
+            if let Some(sig) = self.sign_with_k_recoverable(d, digest, &k) {
+                return sig;
+            }
+        }
+    }
+
+    /// Sign a hash value with private key d and nonce k
+    /// - returns: (signature, recovery id),  None if r = 0 or s = 0 (choose another k)
+    /// - recovery id: bit 0 = y1 mod 2,  bit 1 = (x1 >= n)
+    pub fn sign_with_k_recoverable(
+        &self,
+        d: &T,
+        digest: &[u8],
+        k: &T,
+    ) -> Option<(Signature<T>, u8)> {
+        let n = self.ec.get_order();
+
+        // (x1, y1) = k * G,  r = x1 mod n
+        let p_r = self.ec.mul_base(k);
+        if self.ec.is_zero(&p_r) {
+            return None;
+        }
+        let p_r = self.ec.to_affine(&p_r);
+        let recid = u8::from(p_r.y.test_bit(0)) | (u8::from(p_r.x >= n) << 1);
+        let r = p_r.x % &n;
+        if r.is_zero() {
+            return None;
+        }
+        // s = k^(-1) * (e + r * d) mod n
+        let e = self.bits2int(digest);
+        let s = Number::mod_inv(k, &n) * ((e + r.mul_ref(d)) % &n) % &n;
+        if s.is_zero() {
+            return None;
+        }
+        Some((Signature { r, s }, recid))
+    }
+
+    /// Recover the public key from a signature, its recovery id and the hash value (SEC 1, 4.1.6)
+    /// - Q = r^(-1) * (s * R - e * G),  R: point at x = r + (recid >> 1) * n with y = recid mod 2
+    pub fn recover_public_key(
+        &self,
+        digest: &[u8],
+        sig: &Signature<T>,
+        recid: u8,
+    ) -> Result<Point<T>, EcError> {
+        let n = self.ec.get_order();
+        let p_r = self.recover_r(sig, recid)?;
+
+        // u1 = -e * r^(-1),  u2 = s * r^(-1)
+        let e = self.bits2int(digest) % &n;
+        let ri = Number::mod_inv(&sig.r, &n);
+        let u1 = (n.sub_ref(&e) * &ri) % &n;
+        let u2 = sig.s.mul_ref(&ri) % &n;
+        let q = self.ec.mul2(&self.ec.get_gp(), &u1, &p_r, &u2);
+        if self.ec.is_zero(&q) {
+            return Err(EcError::PointAtInfinity);
+        }
+        Ok(self.ec.to_affine(&q))
+    }
+
+    /// Verify a signature with public key Q
+    pub fn verify(&self, q: &Point<T>, digest: &[u8], sig: &Signature<T>) -> bool {
+        let n = self.ec.get_order();
+        let one = T::one();
+
+        if sig.r < one || sig.r >= n || sig.s < one || sig.s >= n {
+            return false;
+        }
+        if self.ec.is_zero(q) {
+            return false;
+        }
+        // u1 = e * s^(-1),  u2 = r * s^(-1)
+        let e = self.bits2int(digest);
+        let w = Number::mod_inv(&sig.s, &n);
+        let u1 = e * &w % &n;
+        let u2 = sig.r.mul_ref(&w) % &n;
+
+        // (x1, y1) = u1 * G + u2 * Q
+        let p_x = self.ec.mul2(&self.ec.get_gp(), &u1, q, &u2);
+        if self.ec.is_zero(&p_x) {
+            return false;
+        }
+        let v = self.ec.to_affine(&p_x).x % &n;
+        v == sig.r
+    }
+
+    /// Verify a batch of signatures (Q, digest, sig, recid) with one multi-scalar multiplication
+    /// - R_i is rebuilt from (r_i, recid_i):  sum a_i * (u1_i * G + u2_i * Q_i - R_i) = O,
+    ///   a_i random (see batch::verify_equations)
+    /// - stricter than verify:  a signature with a wrong recovery id fails
+    /// - Err(indices of the invalid signatures)
+    pub fn verify_batch(&self, items: &[BatchItem<T>]) -> Result<(), Vec<usize>> {
+        let eqs: Vec<Option<Equation<T>>> = items
+            .iter()
+            .map(|&(q, digest, sig, recid)| self.equation(q, digest, sig, recid))
+            .collect();
+        verify_equations(self.ec, &eqs)
+    }
+
+    /// u1 * G + u2 * Q - R = O
+    fn equation(
+        &self,
+        q: &Point<T>,
+        digest: &[u8],
+        sig: &Signature<T>,
+        recid: u8,
+    ) -> Option<Equation<T>> {
+        let n = self.ec.get_order();
+        if self.ec.is_zero(q) {
+            return None;
+        }
+        let p_r = self.recover_r(sig, recid).ok()?;
+
+        // u1 = e * s^(-1),  u2 = r * s^(-1)
+        let e = self.bits2int(digest) % &n;
+        let w = Number::mod_inv(&sig.s, &n);
+        let u1 = e * &w % &n;
+        let u2 = sig.r.mul_ref(&w) % &n;
+        Some(vec![
+            (self.ec.get_gp(), u1),
+            (q.clone(), u2),
+            (p_r, n - T::one()),
+        ])
+    }
+
+    /// The point R with x(R) = r (+ n if recid & 2) and y parity recid & 1
+    fn recover_r(&self, sig: &Signature<T>, recid: u8) -> Result<Point<T>, EcError> {
+        let n = self.ec.get_order();
+
+        if recid > 3 || sig.r.is_zero() || sig.r >= n || sig.s.is_zero() || sig.s >= n {
+            return Err(EcError::InvalidSignature);
+        }
+        let x = if recid & 2 == 0 {
+            sig.r.clone()
+        } else {
+            sig.r.add_ref(&n)
+        };
+        if x >= self.ec.get_prime() {
+            return Err(EcError::CoordinateOutOfRange);
+        }
+        let p_r = self
+            .ec
+            .point_from_x(&x, u32::from(recid & 1))
+            .ok_or(EcError::NotQuadraticResidue)?;
+        self.ec.check_order(&p_r)?;
+        Ok(p_r)
+    }
+}
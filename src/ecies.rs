@@ -0,0 +1,326 @@
+//!
+//! ECIES (Elliptic Curve Integrated Encryption Scheme, SEC 1 5.1 / ISO 18033-2 ECIES-KEM)
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+use std::marker::PhantomData;
+use std::{error, fmt};
+
+use aes::{Aes128, Aes256};
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use hkdf::SimpleHkdf;
+use hmac::digest::core_api::BlockSizeUser;
+use hmac::digest::Digest;
+use hmac::{Mac, SimpleHmac};
+
+use super::ecdh::Ecdh;
+use super::number::Number;
+use super::{EcError, EcOp, Point, PointFormat};
+
+/// Key derivation function
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kdf {
+    /// ANSI X9.63 KDF (= ISO 18033-2 KDF2):  Hash(Z || counter || SharedInfo) ...
+    X963,
+    /// HKDF (RFC 5869),  no salt,  info = SharedInfo
+    Hkdf,
+}
+
+/// Symmetric encryption
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cipher {
+    /// XOR with KDF output (key length = message length)
+    Xor,
+    /// AES-128 in CTR mode (IV = 0)
+    Aes128Ctr,
+    /// AES-256 in CTR mode (IV = 0)
+    Aes256Ctr,
+}
+
+/// MAC scheme (HMAC with the hash of the scheme, hash length key)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MacScheme {
+    /// Full length tag (e.g. HMAC-SHA-256-256)
+    Hmac,
+    /// Tag truncated to half the hash length (e.g. HMAC-SHA-256-128)
+    HmacHalf,
+}
+
+/// ECIES errors
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EciesError {
+    /// Invalid public key, ephemeral point or private key
+    Point(EcError),
+    /// Ciphertext is too short for the ephemeral point and the tag
+    InvalidLength(usize),
+    /// Tag does not match (wrong key or modified ciphertext)
+    MacMismatch,
+    /// Requested key length is too long for the KDF
+    KdfLength,
+}
+
+impl fmt::Display for EciesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EciesError::Point(e) => write!(f, "{}", e),
+            EciesError::InvalidLength(n) => write!(f, "Invalid ciphertext length: {}", n),
+            EciesError::MacMismatch => write!(f, "MAC check failed"),
+            EciesError::KdfLength => write!(f, "KDF output too long"),
+        }
+    }
+}
+
+impl error::Error for EciesError {}
+
+impl From<EcError> for EciesError {
+    fn from(e: EcError) -> EciesError {
+        EciesError::Point(e)
+    }
+}
+
+/// ECIES on an elliptic curve (any EcOp implementation)
+/// - D: hash function of the KDF and the MAC
+/// - ciphertext: R || EM || tag  (R: encoded ephemeral point)
+pub struct Ecies<'a, T: Number, E: EcOp<T>, D: Digest + BlockSizeUser + Clone> {
+    ec: &'a E,
+    kdf: Kdf,
+    cipher: Cipher,
+    mac: MacScheme,
+    format: PointFormat,
+    shared_info1: Vec<u8>,
+    shared_info2: Vec<u8>,
+    _t: PhantomData<(T, D)>,
+}
+
+impl<'a, T: Number, E: EcOp<T>, D: Digest + BlockSizeUser + Clone> Ecies<'a, T, E, D> {
+    /// X9.63 KDF, AES-128-CTR, full length HMAC, uncompressed ephemeral point
+    pub fn new(ec: &'a E) -> Ecies<'a, T, E, D> {
+        Ecies {
+            ec,
+            kdf: Kdf::X963,
+            cipher: Cipher::Aes128Ctr,
+            mac: MacScheme::Hmac,
+            format: PointFormat::Uncompressed,
+            shared_info1: vec![],
+            shared_info2: vec![],
+            _t: PhantomData,
+        }
+    }
+
+    /// Set the key derivation function
+    pub fn with_kdf(mut self, kdf: Kdf) -> Ecies<'a, T, E, D> {
+        self.kdf = kdf;
+        self
+    }
+
+    /// Set the symmetric encryption scheme
+    pub fn with_cipher(mut self, cipher: Cipher) -> Ecies<'a, T, E, D> {
+        self.cipher = cipher;
+        self
+    }
+
+    /// Set the MAC scheme
+    pub fn with_mac(mut self, mac: MacScheme) -> Ecies<'a, T, E, D> {
+        self.mac = mac;
+        self
+    }
+
+    /// Set the ephemeral point encoding
+    pub fn with_format(mut self, format: PointFormat) -> Ecies<'a, T, E, D> {
+        self.format = format;
+        self
+    }
+
+    /// Set SharedInfo1 (KDF) and SharedInfo2 (MAC)
+    pub fn with_shared_info(mut self, info1: &[u8], info2: &[u8]) -> Ecies<'a, T, E, D> {
+        self.shared_info1 = info1.to_vec();
+        self.shared_info2 = info2.to_vec();
+        self
+    }
+
+    /// Generate key pair (d, Q = d * G),  1 <= d < n  (mul_base, see EcOp::mul_ct)
+    pub fn gen_keypair(&self) -> (T, Point<T>) {
+        let d = Number::gen_rand(&T::one(), &self.ec.get_order());
+        let q = self.ec.to_affine(&self.ec.mul_base(&d));
+        (d, q)
+    }
+
+    /// Tag length in octets
+    pub fn get_tag_len(&self) -> usize {
+        let hlen = <D as Digest>::output_size();
+        match self.mac {
+            MacScheme::Hmac => hlen,
+            MacScheme::HmacHalf => hlen / 2,
+        }
+    }
+
+    /// Encrypt a message to public key Q (random ephemeral key)
+    pub fn encrypt(&self, q: &Point<T>, msg: &[u8]) -> Result<Vec<u8>, EciesError> {
+        let k = Number::gen_rand(&T::one(), &self.ec.get_order());
+        self.encrypt_with_k(q, msg, &k)
+    }
+
+    /// Encrypt a message to public key Q with ephemeral private key k
+    pub fn encrypt_with_k(&self, q: &Point<T>, msg: &[u8], k: &T) -> Result<Vec<u8>, EciesError> {
+        // R = k * G,  z = x(k * Q)
+        let (r, z) = self.encap(q, k)?;
+        let (ek, mk) = self.derive_keys(&z, msg.len())?;
+
+        let mut c = r;
+        let em_pos = c.len();
+        c.extend_from_slice(msg);
+        self.apply_cipher(&ek, &mut c[em_pos..]);
+        let tag = self.tag(&mk, &c[em_pos..]);
+        c.extend_from_slice(&tag);
+        Ok(c)
+    }
+
+    /// Decrypt a ciphertext R || EM || tag with private key d
+    pub fn decrypt(&self, d: &T, c: &[u8]) -> Result<Vec<u8>, EciesError> {
+        let rlen = self.point_len(c)?;
+        let tlen = self.get_tag_len();
+        if c.len() < rlen + tlen {
+            return Err(EciesError::InvalidLength(c.len()));
+        }
+        let z = self.decap(d, &c[..rlen])?;
+        let (em, tag) = c[rlen..].split_at(c.len() - rlen - tlen);
+        let (ek, mk) = self.derive_keys(&z, em.len())?;
+
+        // constant-time tag comparison
+        let diff = self
+            .tag(&mk, em)
+            .iter()
+            .zip(tag.iter())
+            .fold(0u8, |a, (x, y)| a | (x ^ y));
+        if diff != 0 {
+            return Err(EciesError::MacMismatch);
+        }
+        let mut m = em.to_vec();
+        self.apply_cipher(&ek, &mut m);
+        Ok(m)
+    }
+
+    /// ECIES-KEM encapsulation (ISO 18033-2, 10.2.3):  (C0, K = KDF(C0 || PEH, key_len))
+    pub fn kem_encap(
+        &self,
+        q: &Point<T>,
+        key_len: usize,
+    ) -> Result<(Vec<u8>, Vec<u8>), EciesError> {
+        let k = Number::gen_rand(&T::one(), &self.ec.get_order());
+        self.kem_encap_with_k(q, key_len, &k)
+    }
+
+    /// ECIES-KEM encapsulation with ephemeral private key k
+    pub fn kem_encap_with_k(
+        &self,
+        q: &Point<T>,
+        key_len: usize,
+        k: &T,
+    ) -> Result<(Vec<u8>, Vec<u8>), EciesError> {
+        let (c0, peh) = self.encap(q, k)?;
+        let key = self.kdf(&[&c0[..], &peh].concat(), &[], key_len)?;
+        Ok((c0, key))
+    }
+
+    /// ECIES-KEM decapsulation (ISO 18033-2, 10.2.4)
+    pub fn kem_decap(&self, d: &T, c0: &[u8], key_len: usize) -> Result<Vec<u8>, EciesError> {
+        if self.point_len(c0)? != c0.len() {
+            return Err(EciesError::InvalidLength(c0.len()));
+        }
+        let peh = self.decap(d, c0)?;
+        self.kdf(&[c0, &peh].concat(), &[], key_len)
+    }
+
+    /// (encoded R = k * G, x(k * Q))
+    /// - the secret k only goes through mul_base and mul_ct:  the point operation sequence
+    ///   does not depend on k (the big number arithmetic is not constant time)
+    fn encap(&self, q: &Point<T>, k: &T) -> Result<(Vec<u8>, Vec<u8>), EciesError> {
+        let z = Ecdh::new(self.ec).derive_point(k, q)?;
+        let r = self.ec.encode_point(&self.ec.mul_base(k), self.format);
+        Ok((r, z))
+    }
+
+    /// x(d * R) for encoded R (full validation of R in derive_point)
+    fn decap(&self, d: &T, r: &[u8]) -> Result<Vec<u8>, EciesError> {
+        let p_r = self.ec.try_decode_point_partial(r)?;
+        Ok(Ecdh::new(self.ec).derive_point(d, &p_r)?)
+    }
+
+    /// Length of the encoded ephemeral point at the head of c
+    fn point_len(&self, c: &[u8]) -> Result<usize, EciesError> {
+        let ln = self.ec.get_field_len();
+        match c.first() {
+            Some(0x02 | 0x03) => Ok(ln + 1),
+            Some(0x04 | 0x06 | 0x07) => Ok(2 * ln + 1),
+            Some(&b) => Err(EcError::InvalidPrefix(b).into()),
+            None => Err(EciesError::InvalidLength(0)),
+        }
+    }
+
+    /// (encryption key, MAC key) = KDF(z, SharedInfo1)
+    fn derive_keys(&self, z: &[u8], mlen: usize) -> Result<(Vec<u8>, Vec<u8>), EciesError> {
+        let eklen = match self.cipher {
+            Cipher::Xor => mlen,
+            Cipher::Aes128Ctr => 16,
+            Cipher::Aes256Ctr => 32,
+        };
+        let mklen = <D as Digest>::output_size();
+        let mut k = self.kdf(z, &self.shared_info1, eklen + mklen)?;
+        let mk = k.split_off(eklen);
+        Ok((k, mk))
+    }
+
+    /// KDF(z, info) of len octets
+    /// - at most (2^32 - 1) hash blocks (X9.63, 32-bit counter) or 255 hash blocks (HKDF)
+    fn kdf(&self, z: &[u8], info: &[u8], len: usize) -> Result<Vec<u8>, EciesError> {
+        let hlen = <D as Digest>::output_size();
+        let blocks = match self.kdf {
+            Kdf::X963 => u64::from(u32::MAX),
+            Kdf::Hkdf => 255,
+        };
+        if len as u64 > hlen as u64 * blocks {
+            return Err(EciesError::KdfLength);
+        }
+        let mut k = vec![0u8; len];
+        match self.kdf {
+            Kdf::X963 => {
+                // K_i = Hash(z || i || info),  i = 1, 2, ..  (32-bit big-endian counter)
+                for (i, chunk) in k.chunks_mut(hlen).enumerate() {
+                    let mut h = D::new();
+                    h.update(z);
+                    h.update((i as u32 + 1).to_be_bytes());
+                    h.update(info);
+                    chunk.copy_from_slice(&h.finalize()[..chunk.len()]);
+                }
+            }
+            Kdf::Hkdf => SimpleHkdf::<D>::new(None, z)
+                .expand(info, &mut k)
+                .map_err(|_| EciesError::KdfLength)?,
+        }
+        Ok(k)
+    }
+
+    /// Encrypt / decrypt in place
+    fn apply_cipher(&self, ek: &[u8], buf: &mut [u8]) {
+        let iv = [0u8; 16];
+        match self.cipher {
+            Cipher::Xor => buf.iter_mut().zip(ek).for_each(|(b, k)| *b ^= k),
+            Cipher::Aes128Ctr => {
+                ctr::Ctr128BE::<Aes128>::new(ek.into(), &iv.into()).apply_keystream(buf)
+            }
+            Cipher::Aes256Ctr => {
+                ctr::Ctr128BE::<Aes256>::new(ek.into(), &iv.into()).apply_keystream(buf)
+            }
+        }
+    }
+
+    /// MAC(mk, EM || SharedInfo2)
+    fn tag(&self, mk: &[u8], em: &[u8]) -> Vec<u8> {
+        let mut mac = <SimpleHmac<D> as Mac>::new_from_slice(mk).unwrap();
+        mac.update(em);
+        mac.update(&self.shared_info2);
+        let mut t = mac.finalize().into_bytes().to_vec();
+        t.truncate(self.get_tag_len());
+        t
+    }
+}
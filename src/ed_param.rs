@@ -0,0 +1,124 @@
+//!
+//! Twisted Edwards Curve Parameter Interface
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+
+use super::ec_param::UnknownCurve;
+use super::number::Number;
+
+///   Twisted Edwards curve parameters  (a * x^2 + y^2 = 1 + d * x^2 * y^2)
+///   (no ParamOp:  the short Weierstrass curves EcpA / EcpJ cannot be built from them)
+///   id,  oid,  a  d,  p,  g (04||X||Y),  order,   h
+#[derive(Clone, Copy)]
+pub struct EdParam<'a> {
+    pub id: &'a str,
+    pub oid: &'a str,
+    pub a: &'a [u8],
+    pub d: &'a [u8],
+    pub p: &'a [u8],
+    pub g: &'a [u8],
+    pub n: &'a [u8],
+    pub h: &'a [u8],
+}
+
+///   RFC 8032 (RFC 7748) curves,  oid: id-Ed25519 / id-Ed448 (RFC 8410)
+static CURVE_PARAMS: [EdParam; 2] = [
+    EdParam {
+        id: "edwards25519",
+        oid: "1.3.101.112",
+        a: b"7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEC",
+        d: b"52036CEE2B6FFE738CC740797779E89800700A4D4141D8AB75EB4DCA135978A3",
+        p: b"7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFED",
+        g: b"04216936D3CD6E53FEC0A4E231FDD6DC5C692CC7609525A7B2C9562D608F25D51A6666666666666666666666666666666666666666666666666666666666666658",
+        n: b"1000000000000000000000000000000014DEF9DEA2F79CD65812631A5CF5D3ED",
+        h: b"08",
+    },
+    EdParam {
+        id: "edwards448",
+        oid: "1.3.101.113",
+        a: b"01",
+        d: b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF6756",
+        p: b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+        g: b"044F1970C66BED0DED221D15A622BF36DA9E146570470F1767EA6DE324A3D3A46412AE1AF72AB66511433B80E18B00938E2626A82BC70CC05E693F46716EB6BC248876203756C9C7624BEA73736CA3984087789C1E05A0C2D73AD3FF1CE67C39C4FDBD132C4ED7C8AD9808795BF230FA14",
+        n: b"3FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF7CCA23E9C44EDB49AED63690216CC2728DC58F552378C292AB5844F3",
+        h: b"04",
+    },
+];
+
+///   Curve names of the signature schemes
+static CURVE_ALIASES: [(&str, &str); 2] = [("Ed25519", "edwards25519"), ("Ed448", "edwards448")];
+
+impl EdParam<'_> {
+    /// Curve parameters by id ("edwards25519", "edwards448", "Ed25519", "Ed448")
+    /// - panics if the curve is unknown (see try_new)
+    pub fn new(ecid: &str) -> EdParam<'static> {
+        Self::try_new(ecid).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Curve parameters by id ("edwards25519", "edwards448", "Ed25519", "Ed448")
+    pub fn try_new(ecid: &str) -> Result<EdParam<'static>, UnknownCurve> {
+        let id = CURVE_ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == ecid)
+            .map_or(ecid, |(_, id)| *id);
+        CURVE_PARAMS
+            .iter()
+            .find(|prm| prm.id == id)
+            .copied()
+            .ok_or_else(|| UnknownCurve(ecid.to_string()))
+    }
+
+    /// Supported curve ids
+    pub fn curve_ids() -> Vec<&'static str> {
+        CURVE_PARAMS.iter().map(|prm| prm.id).collect()
+    }
+
+    /// Curve parameters by object identifier (e.g. "1.3.101.112")
+    pub fn from_oid(oid: &str) -> Option<EdParam<'static>> {
+        CURVE_PARAMS.iter().find(|prm| prm.oid == oid).copied()
+    }
+
+    pub fn get_ecid(&self) -> &str {
+        self.id
+    }
+
+    pub fn get_oid(&self) -> &str {
+        self.oid
+    }
+
+    /// Get prime p
+    pub fn get_prime<T: Number>(&self) -> T {
+        T::from_bytes_radix(self.p, 16)
+    }
+
+    /// Get coefficient a
+    pub fn get_a<T: Number>(&self) -> T {
+        T::from_bytes_radix(self.a, 16)
+    }
+
+    /// Get coefficient d
+    pub fn get_d<T: Number>(&self) -> T {
+        T::from_bytes_radix(self.d, 16)
+    }
+
+    /// Get order
+    pub fn get_order<T: Number>(&self) -> T {
+        T::from_bytes_radix(self.n, 16)
+    }
+
+    /// Get cofactor
+    pub fn get_cofactor<T: Number>(&self) -> T {
+        T::from_bytes_radix(self.h, 16)
+    }
+
+    pub fn get_gx<T: Number>(&self) -> T {
+        let ln = (self.g.len() - 2) / 2;
+        T::from_bytes_radix(&self.g[2..(ln + 2)], 16)
+    }
+
+    pub fn get_gy<T: Number>(&self) -> T {
+        let ln = (self.g.len() - 2) / 2;
+        T::from_bytes_radix(&self.g[(ln + 2)..], 16)
+    }
+}
@@ -0,0 +1,243 @@
+//!
+//! EdDSA (Edwards-curve Digital Signature Algorithm, RFC 8032)
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+use rand::RngCore;
+use sha2::{Digest, Sha512};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+
+use super::ecc_e::EcpE;
+use super::number::Number;
+use super::{EcError, EcOp};
+
+/// Ed25519 (SHA-512, dom2) or Ed448 (SHAKE256, dom4)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Scheme {
+    Ed25519,
+    Ed448,
+}
+
+/// EdDSA on edwards25519 (Ed25519, Ed25519ctx, Ed25519ph) or edwards448 (Ed448, Ed448ph)
+/// - keys and signatures are octet strings:  public key ENC(A),  signature ENC(R) || S
+pub struct EdDsa<'a, T: Number> {
+    ec: &'a EcpE<T>,
+    scheme: Scheme,
+}
+
+impl<'a, T: Number> EdDsa<'a, T> {
+    /// EdDSA on the curve ec
+    /// - panics if ec is not edwards25519 or edwards448
+    pub fn new(ec: &'a EcpE<T>) -> EdDsa<'a, T> {
+        let scheme = match ec.id {
+            "edwards25519" => Scheme::Ed25519,
+            "edwards448" => Scheme::Ed448,
+            id => panic!("EdDSA is not defined on {}", id),
+        };
+        EdDsa { ec, scheme }
+    }
+
+    /// Length of the secret key, the public key and each half of the signature (b / 8)
+    pub fn get_len(&self) -> usize {
+        self.ec.get_encoded_len()
+    }
+
+    /// Generate key pair (secret key, public key)
+    pub fn gen_keypair(&self) -> (Vec<u8>, Vec<u8>) {
+        let mut sk = vec![0u8; self.get_len()];
+        rand::thread_rng().fill_bytes(&mut sk);
+        let pk = self.public_key(&sk).unwrap();
+        (sk, pk)
+    }
+
+    /// Public key ENC(s * B) of a secret key
+    /// - s and the nonce r of sign go through mul_base of EcpE:  the point operation sequence
+    ///   does not depend on them (the big number arithmetic is not constant time)
+    pub fn public_key(&self, sk: &[u8]) -> Result<Vec<u8>, EcError> {
+        let (s, _) = self.expand(sk)?;
+        Ok(self.ec.encode_ed(&self.ec.mul_base(&s)))
+    }
+
+    /// Sign a message (Ed25519 / Ed448 with an empty context)
+    pub fn sign(&self, sk: &[u8], msg: &[u8]) -> Result<Vec<u8>, EcError> {
+        let dom = match self.scheme {
+            Scheme::Ed25519 => vec![],
+            Scheme::Ed448 => self.dom(0, &[])?,
+        };
+        self.sign_dom(sk, msg, &dom)
+    }
+
+    /// Sign a message with a context of up to 255 octets (Ed25519ctx / Ed448)
+    /// - Ed25519ctx needs a non-empty context (RFC 8032, 8.3)
+    pub fn sign_ctx(&self, sk: &[u8], msg: &[u8], ctx: &[u8]) -> Result<Vec<u8>, EcError> {
+        self.sign_dom(sk, msg, &self.dom_ctx(ctx)?)
+    }
+
+    /// Sign the hash PH(msg) of a message (Ed25519ph:  SHA-512,  Ed448ph:  SHAKE256, 64 octets)
+    pub fn sign_ph(&self, sk: &[u8], msg: &[u8], ctx: &[u8]) -> Result<Vec<u8>, EcError> {
+        self.sign_dom(sk, &self.prehash(msg), &self.dom(1, ctx)?)
+    }
+
+    /// Verify a signature (Ed25519 / Ed448 with an empty context)
+    pub fn verify(&self, pk: &[u8], msg: &[u8], sig: &[u8]) -> bool {
+        let dom = match self.scheme {
+            Scheme::Ed25519 => vec![],
+            Scheme::Ed448 => self.dom(0, &[]).unwrap(),
+        };
+        self.verify_dom(pk, msg, sig, &dom)
+    }
+
+    /// Verify a signature with a context (Ed25519ctx / Ed448)
+    /// - false for Ed25519ctx with an empty context
+    pub fn verify_ctx(&self, pk: &[u8], msg: &[u8], ctx: &[u8], sig: &[u8]) -> bool {
+        match self.dom_ctx(ctx) {
+            Ok(dom) => self.verify_dom(pk, msg, sig, &dom),
+            Err(_) => false,
+        }
+    }
+
+    /// Verify a signature of PH(msg) (Ed25519ph / Ed448ph)
+    pub fn verify_ph(&self, pk: &[u8], msg: &[u8], ctx: &[u8], sig: &[u8]) -> bool {
+        match self.dom(1, ctx) {
+            Ok(dom) => self.verify_dom(pk, &self.prehash(msg), sig, &dom),
+            Err(_) => false,
+        }
+    }
+
+    /// R = r * B,  S = r + H(dom || ENC(R) || ENC(A) || M) * s mod L,
+    /// r = H(dom || prefix || M) mod L
+    fn sign_dom(&self, sk: &[u8], msg: &[u8], dom: &[u8]) -> Result<Vec<u8>, EcError> {
+        let n = self.ec.get_order();
+        let (s, prefix) = self.expand(sk)?;
+        let pk = self.ec.encode_ed(&self.ec.mul_base(&s));
+
+        let r = self.hash_int(&[dom, &prefix, msg]);
+        let mut sig = self.ec.encode_ed(&self.ec.mul_base(&r));
+        let k = self.hash_int(&[dom, &sig, &pk, msg]);
+        let s = (r + k * &s) % &n;
+        sig.extend_from_slice(&to_bytes_le(&s, self.get_len()));
+        Ok(sig)
+    }
+
+    /// [2^c] * S * B = [2^c] * R + [2^c] * k * A  (cofactored)
+    fn verify_dom(&self, pk: &[u8], msg: &[u8], sig: &[u8], dom: &[u8]) -> bool {
+        let n = self.ec.get_order();
+        let len = self.get_len();
+        if sig.len() != 2 * len {
+            return false;
+        }
+        let (p_r, p_a) = match (self.ec.decode_ed(&sig[..len]), self.ec.decode_ed(pk)) {
+            (Ok(p_r), Ok(p_a)) => (p_r, p_a),
+            _ => return false,
+        };
+        let s = from_bytes_le::<T>(&sig[len..]);
+        if s >= n {
+            return false;
+        }
+        let k = self.hash_int(&[dom, &sig[..len], pk, msg]);
+
+        // h * (S * B - k * A - R) = O
+        let p_p = self
+            .ec
+            .mul2(&self.ec.get_gp(), &s, &p_a, &(n.sub_ref(&k) % &n));
+        let p_p = self.ec.sub(&p_p, &p_r);
+        self.ec.is_zero(&self.ec.mul(&p_p, &self.ec.get_cofac()))
+    }
+
+    /// (s, prefix):  H(sk) = h_0 || h_1,  s = h_0 pruned,  prefix = h_1
+    /// - s: the lowest c bits cleared (h = 2^c),  bit n = bitlen(p) - 1 set,  higher bits cleared
+    fn expand(&self, sk: &[u8]) -> Result<(T, Vec<u8>), EcError> {
+        let len = self.get_len();
+        if sk.len() != len {
+            return Err(EcError::InvalidLength(sk.len()));
+        }
+        let mut h = self.hash(&[sk]);
+        let prefix = h.split_off(len);
+
+        let c = self.ec.get_cofac().bit_len() - 1;
+        let top = self.ec.get_prime().bit_len() - 1;
+        h[0] &= !((1u8 << c) - 1);
+        for i in top..(8 * len) {
+            h[i / 8] &= !(1u8 << (i % 8));
+        }
+        h[top / 8] |= 1 << (top % 8);
+        Ok((from_bytes_le(&h), prefix))
+    }
+
+    /// dom of sign_ctx / verify_ctx:  Ed25519ctx SHOULD NOT be used with an empty context
+    /// (RFC 8032, 8.3),  Ed448 takes any context
+    fn dom_ctx(&self, ctx: &[u8]) -> Result<Vec<u8>, EcError> {
+        if self.scheme == Scheme::Ed25519 && ctx.is_empty() {
+            return Err(EcError::InvalidLength(0));
+        }
+        self.dom(0, ctx)
+    }
+
+    /// dom2(x, y) / dom4(x, y):  prefix || octet(x) || octet(len(y)) || y
+    fn dom(&self, phflag: u8, ctx: &[u8]) -> Result<Vec<u8>, EcError> {
+        if ctx.len() > 255 {
+            return Err(EcError::InvalidLength(ctx.len()));
+        }
+        let prefix: &[u8] = match self.scheme {
+            Scheme::Ed25519 => b"SigEd25519 no Ed25519 collisions",
+            Scheme::Ed448 => b"SigEd448",
+        };
+        let mut dom = prefix.to_vec();
+        dom.push(phflag);
+        dom.push(ctx.len() as u8);
+        dom.extend_from_slice(ctx);
+        Ok(dom)
+    }
+
+    /// H:  SHA-512 (Ed25519),  SHAKE256 with 114 octets output (Ed448)
+    fn hash(&self, data: &[&[u8]]) -> Vec<u8> {
+        match self.scheme {
+            Scheme::Ed25519 => {
+                let mut h = Sha512::new();
+                for d in data {
+                    Digest::update(&mut h, d);
+                }
+                h.finalize().to_vec()
+            }
+            Scheme::Ed448 => shake256(data, 2 * self.get_len()),
+        }
+    }
+
+    /// H(data) mod L  (little-endian)
+    fn hash_int(&self, data: &[&[u8]]) -> T {
+        from_bytes_le::<T>(&self.hash(data)) % &self.ec.get_order()
+    }
+
+    /// PH:  SHA-512 (Ed25519ph),  SHAKE256 with 64 octets output (Ed448ph)
+    fn prehash(&self, msg: &[u8]) -> Vec<u8> {
+        match self.scheme {
+            Scheme::Ed25519 => Sha512::digest(msg).to_vec(),
+            Scheme::Ed448 => shake256(&[msg], 64),
+        }
+    }
+}
+
+/// SHAKE256(data, len)
+fn shake256(data: &[&[u8]], len: usize) -> Vec<u8> {
+    let mut h = Shake256::default();
+    for d in data {
+        h.update(d);
+    }
+    let mut out = vec![0u8; len];
+    h.finalize_xof().read(&mut out);
+    out
+}
+
+/// Little-endian octets to integer
+fn from_bytes_le<T: Number>(b: &[u8]) -> T {
+    let be: Vec<u8> = b.iter().rev().copied().collect();
+    T::from_bytes_be(&be)
+}
+
+/// Integer to len little-endian octets
+fn to_bytes_le<T: Number>(x: &T, len: usize) -> Vec<u8> {
+    let mut b = x.to_bytes_be_pad(len);
+    b.reverse();
+    b
+}
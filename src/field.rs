@@ -0,0 +1,245 @@
+//!
+//! Prime Field Arithmetic (GF(p))
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use super::number::Number;
+use super::reduction::Reduction;
+
+///   Prime field GF(p)
+///   Elements are kept canonical in [0, p) (in the internal representation of the reduction)
+#[derive(Clone, Debug)]
+pub struct PrimeField<T: Number> {
+    red: Reduction<T>,
+}
+
+impl<T: Number> PrimeField<T> {
+    /// Field of a prime p (Montgomery form)
+    pub fn new(p: &T) -> PrimeField<T> {
+        PrimeField {
+            red: Reduction::new(p),
+        }
+    }
+
+    /// Field of a prime p (special-form reduction if p has that form, see Reduction::for_prime)
+    pub fn for_prime(p: &T) -> PrimeField<T> {
+        PrimeField {
+            red: Reduction::for_prime(p),
+        }
+    }
+
+    /// Reduction used by the field arithmetic
+    pub fn get_reduction(&self) -> &Reduction<T> {
+        &self.red
+    }
+
+    /// Get modulus p
+    pub fn get_prime(&self) -> &T {
+        self.red.get_prime()
+    }
+
+    /// Element x mod p (x: any integer)
+    pub fn elem(&self, x: &T) -> FieldElement<'_, T> {
+        FieldElement {
+            f: self,
+            v: Cow::Owned(self.red.to_field(x)),
+        }
+    }
+
+    pub fn zero(&self) -> FieldElement<'_, T> {
+        self.elem_from_repr(T::zero())
+    }
+
+    pub fn one(&self) -> FieldElement<'_, T> {
+        self.elem_from_repr(self.red.one())
+    }
+
+    /// Element from the internal representation (no conversion)
+    pub(crate) fn elem_from_repr(&self, v: T) -> FieldElement<'_, T> {
+        FieldElement {
+            f: self,
+            v: Cow::Owned(v),
+        }
+    }
+
+    /// Element borrowing an internal representation
+    pub(crate) fn elem_ref<'a>(&'a self, v: &'a T) -> FieldElement<'a, T> {
+        FieldElement {
+            f: self,
+            v: Cow::Borrowed(v),
+        }
+    }
+}
+
+///   Element of GF(p),  always in [0, p)
+#[derive(Clone, Debug)]
+pub struct FieldElement<'a, T: Number> {
+    f: &'a PrimeField<T>,
+    /// internal representation
+    v: Cow<'a, T>,
+}
+
+impl<'a, T: Number> FieldElement<'a, T> {
+    /// Get the field
+    pub fn field(&self) -> &'a PrimeField<T> {
+        self.f
+    }
+
+    /// Integer value in [0, p)
+    pub fn value(&self) -> T {
+        self.f.red.from_field(&self.v)
+    }
+
+    /// Internal representation
+    pub(crate) fn into_repr(self) -> T {
+        self.v.into_owned()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.v.is_zero()
+    }
+
+    pub fn is_one(&self) -> bool {
+        self.f.red.is_one(&self.v)
+    }
+
+    /// Parity of the value (y coordinate sign)
+    pub fn is_odd(&self) -> bool {
+        self.value().test_bit(0)
+    }
+
+    /// self^2
+    pub fn square(&self) -> FieldElement<'a, T> {
+        self.f.elem_from_repr(self.f.red.sqr(&self.v))
+    }
+
+    /// 2 * self
+    pub fn double(&self) -> FieldElement<'a, T> {
+        self.f.elem_from_repr(self.f.red.add(&self.v, &self.v))
+    }
+
+    /// self / 2
+    pub fn half(&self) -> FieldElement<'a, T> {
+        self.f.elem_from_repr(self.f.red.half(&self.v))
+    }
+
+    /// self^e  (e >= 0, left-to-right binary method)
+    pub fn pow(&self, e: &T) -> FieldElement<'a, T> {
+        let mut r = self.f.one();
+        for i in (0..e.bit_len()).rev() {
+            r = r.square();
+            if e.test_bit(i) {
+                r = &r * self;
+            }
+        }
+        r
+    }
+
+    /// self^(-1),  None for 0
+    pub fn inv(&self) -> Option<FieldElement<'a, T>> {
+        if self.is_zero() {
+            return None;
+        }
+        let p = self.f.get_prime();
+        Some(self.f.elem(&Number::mod_inv(&self.value(), p)))
+    }
+
+    /// Square root,  None if self is not a quadratic residue
+    pub fn sqrt(&self) -> Option<FieldElement<'a, T>> {
+        let r = Number::mod_sqrt(&self.value(), self.f.get_prime())?;
+        Some(self.f.elem(&r))
+    }
+}
+
+impl<T: Number> PartialEq for FieldElement<'_, T> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.v == rhs.v && self.f.get_prime() == rhs.f.get_prime()
+    }
+}
+
+impl<T: Number> fmt::Display for FieldElement<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value())
+    }
+}
+
+impl<'a, T: Number> Add<&FieldElement<'a, T>> for &FieldElement<'a, T> {
+    type Output = FieldElement<'a, T>;
+
+    fn add(self, rhs: &FieldElement<'a, T>) -> FieldElement<'a, T> {
+        self.f.elem_from_repr(self.f.red.add(&self.v, &rhs.v))
+    }
+}
+
+impl<'a, T: Number> Sub<&FieldElement<'a, T>> for &FieldElement<'a, T> {
+    type Output = FieldElement<'a, T>;
+
+    fn sub(self, rhs: &FieldElement<'a, T>) -> FieldElement<'a, T> {
+        self.f.elem_from_repr(self.f.red.sub(&self.v, &rhs.v))
+    }
+}
+
+impl<'a, T: Number> Mul<&FieldElement<'a, T>> for &FieldElement<'a, T> {
+    type Output = FieldElement<'a, T>;
+
+    fn mul(self, rhs: &FieldElement<'a, T>) -> FieldElement<'a, T> {
+        self.f.elem_from_repr(self.f.red.mul(&self.v, &rhs.v))
+    }
+}
+
+impl<'a, T: Number> Neg for &FieldElement<'a, T> {
+    type Output = FieldElement<'a, T>;
+
+    fn neg(self) -> FieldElement<'a, T> {
+        self.f.elem_from_repr(self.f.red.sub(&T::zero(), &self.v))
+    }
+}
+
+impl<'a, T: Number> Neg for FieldElement<'a, T> {
+    type Output = FieldElement<'a, T>;
+
+    #[inline]
+    fn neg(self) -> FieldElement<'a, T> {
+        -&self
+    }
+}
+
+/// Owned operands (forward to the reference impl)
+macro_rules! forward_binop {
+    ($imp:ident, $method:ident) => {
+        impl<'a, T: Number> $imp<FieldElement<'a, T>> for FieldElement<'a, T> {
+            type Output = FieldElement<'a, T>;
+
+            #[inline]
+            fn $method(self, rhs: FieldElement<'a, T>) -> FieldElement<'a, T> {
+                (&self).$method(&rhs)
+            }
+        }
+
+        impl<'a, T: Number> $imp<&FieldElement<'a, T>> for FieldElement<'a, T> {
+            type Output = FieldElement<'a, T>;
+
+            #[inline]
+            fn $method(self, rhs: &FieldElement<'a, T>) -> FieldElement<'a, T> {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl<'a, T: Number> $imp<FieldElement<'a, T>> for &FieldElement<'a, T> {
+            type Output = FieldElement<'a, T>;
+
+            #[inline]
+            fn $method(self, rhs: FieldElement<'a, T>) -> FieldElement<'a, T> {
+                self.$method(&rhs)
+            }
+        }
+    };
+}
+
+forward_binop!(Add, add);
+forward_binop!(Sub, sub);
+forward_binop!(Mul, mul);
@@ -0,0 +1,720 @@
+//!
+//! Fixed-width integer (stack allocated) for Number
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+use num_traits::{One, Zero};
+use rand::Rng;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, BitAnd, Div, Mul, Neg, Rem, Shr, Sub};
+
+use super::number::Number;
+
+/// Signed integer of L 64-bit limbs (sign and magnitude, little-endian limbs)
+///
+/// A value must hold the unreduced results of the Number operations, not only
+/// field elements:  the EC code multiplies up to three field elements before
+/// reducing ((x^2 + a) * x + b), so a field of k limbs needs L >= 3k + 1
+/// (3k limbs for the product, one for the additions).
+/// Arithmetic panics on overflow;  the curve constructors reject fields that do
+/// not fit (see number::fits_field), so that decoded input stays in range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FixInt<const L: usize> {
+    neg: bool,
+    mag: [u64; L],
+}
+
+/// Fields of up to 256 bits (4 limbs):  13 limbs of storage
+pub type Fix256 = FixInt<13>;
+/// Fields of up to 384 bits (6 limbs):  19 limbs of storage
+pub type Fix384 = FixInt<19>;
+/// Fields of up to 576 bits (9 limbs, P-521):  28 limbs of storage
+pub type Fix521 = FixInt<28>;
+
+impl<const L: usize> FixInt<L> {
+    fn new(neg: bool, mag: [u64; L]) -> FixInt<L> {
+        FixInt {
+            neg: neg && !is_zero(&mag),
+            mag,
+        }
+    }
+
+    /// Absolute value
+    pub fn abs(&self) -> FixInt<L> {
+        FixInt::new(false, self.mag)
+    }
+
+    /// Is negative ?
+    pub fn is_negative(&self) -> bool {
+        self.neg
+    }
+}
+
+/// Number of significant limbs
+fn limbs(a: &[u64]) -> usize {
+    a.iter().rposition(|&x| x != 0).map_or(0, |i| i + 1)
+}
+
+fn is_zero(a: &[u64]) -> bool {
+    a.iter().all(|&x| x == 0)
+}
+
+fn mag_cmp(a: &[u64], b: &[u64]) -> Ordering {
+    a.iter().rev().cmp(b.iter().rev())
+}
+
+fn mag_add<const L: usize>(a: &[u64; L], b: &[u64; L]) -> [u64; L] {
+    let mut r = [0u64; L];
+    let mut c = false;
+    for i in 0..L {
+        let (s, c1) = a[i].overflowing_add(b[i]);
+        let (s, c2) = s.overflowing_add(c as u64);
+        r[i] = s;
+        c = c1 || c2;
+    }
+    assert!(!c, "FixInt overflow");
+    r
+}
+
+/// a - b  (a >= b)
+fn mag_sub<const L: usize>(a: &[u64; L], b: &[u64; L]) -> [u64; L] {
+    let mut r = [0u64; L];
+    let mut c = false;
+    for i in 0..L {
+        let (s, c1) = a[i].overflowing_sub(b[i]);
+        let (s, c2) = s.overflowing_sub(c as u64);
+        r[i] = s;
+        c = c1 || c2;
+    }
+    debug_assert!(!c);
+    r
+}
+
+fn mag_mul<const L: usize>(a: &[u64; L], b: &[u64; L]) -> [u64; L] {
+    let (la, lb) = (limbs(a), limbs(b));
+    if la + lb <= L {
+        // no overflow possible
+        let mut r = [0u64; L];
+        for i in 0..la {
+            let mut c = 0u128;
+            for j in 0..lb {
+                let t = a[i] as u128 * b[j] as u128 + r[i + j] as u128 + c;
+                r[i + j] = t as u64;
+                c = t >> 64;
+            }
+            r[i + lb] = c as u64;
+        }
+        return r;
+    }
+    let mut w = [[0u64; L]; 2];
+    let w = w.as_flattened_mut();
+    for i in 0..la {
+        let mut c = 0u128;
+        for j in 0..lb {
+            let t = a[i] as u128 * b[j] as u128 + w[i + j] as u128 + c;
+            w[i + j] = t as u64;
+            c = t >> 64;
+        }
+        w[i + lb] = c as u64;
+    }
+    assert!(is_zero(&w[L..]), "FixInt overflow");
+    let mut r = [0u64; L];
+    r.copy_from_slice(&w[..L]);
+    r
+}
+
+/// (a * m + d) for small m, d
+fn mag_mul_small<const L: usize>(a: &[u64; L], m: u64, d: u64) -> [u64; L] {
+    let mut r = [0u64; L];
+    let mut c = d as u128;
+    for i in 0..L {
+        let t = a[i] as u128 * m as u128 + c;
+        r[i] = t as u64;
+        c = t >> 64;
+    }
+    assert!(c == 0, "FixInt overflow");
+    r
+}
+
+/// (a / d, a % d) for small d
+fn mag_divrem_small<const L: usize>(a: &[u64; L], d: u64) -> ([u64; L], u64) {
+    let mut q = [0u64; L];
+    let mut r = 0u128;
+    for i in (0..limbs(a)).rev() {
+        let t = (r << 64) | a[i] as u128;
+        q[i] = (t / d as u128) as u64;
+        r = t % d as u128;
+    }
+    (q, r as u64)
+}
+
+/// (a / b, a % b)  (Knuth, TAOCP Vol.2 4.3.1 Algorithm D)
+#[allow(clippy::needless_range_loop)]
+fn mag_divrem<const L: usize>(a: &[u64; L], b: &[u64; L]) -> ([u64; L], [u64; L]) {
+    let (m, n) = (limbs(a), limbs(b));
+    assert!(n > 0, "FixInt division by zero");
+    if mag_cmp(a, b) == Ordering::Less {
+        return ([0u64; L], *a);
+    }
+    if n == 1 {
+        let (q, r) = mag_divrem_small(a, b[0]);
+        let mut rm = [0u64; L];
+        rm[0] = r;
+        return (q, rm);
+    }
+
+    // normalize: top bit of v set
+    let s = b[n - 1].leading_zeros();
+    let shl = |x: u64, lo: u64| {
+        if s == 0 {
+            x
+        } else {
+            (x << s) | (lo >> (64 - s))
+        }
+    };
+    let mut v = [0u64; L];
+    for i in (1..n).rev() {
+        v[i] = shl(b[i], b[i - 1]);
+    }
+    v[0] = b[0] << s;
+    let mut u = [[0u64; L]; 2];
+    let u = u.as_flattened_mut();
+    u[m] = if s == 0 { 0 } else { a[m - 1] >> (64 - s) };
+    for i in (1..m).rev() {
+        u[i] = shl(a[i], a[i - 1]);
+    }
+    u[0] = a[0] << s;
+
+    let b64 = 1u128 << 64;
+    let mut q = [0u64; L];
+    for j in (0..=(m - n)).rev() {
+        // estimate qhat
+        let num = ((u[j + n] as u128) << 64) | u[j + n - 1] as u128;
+        let mut qhat = num / v[n - 1] as u128;
+        let mut rhat = num % v[n - 1] as u128;
+        while qhat >= b64 || qhat * v[n - 2] as u128 > ((rhat << 64) | u[j + n - 2] as u128) {
+            qhat -= 1;
+            rhat += v[n - 1] as u128;
+            if rhat >= b64 {
+                break;
+            }
+        }
+        // u[j..j+n] -= qhat * v
+        let mut borrow = 0i128;
+        let mut c = 0u128;
+        for i in 0..n {
+            let p = qhat * v[i] as u128 + c;
+            c = p >> 64;
+            let t = u[i + j] as i128 - borrow - (p as u64) as i128;
+            u[i + j] = t as u64;
+            borrow = (t < 0) as i128;
+        }
+        let t = u[j + n] as i128 - borrow - c as i128;
+        u[j + n] = t as u64;
+        q[j] = qhat as u64;
+
+        // add back
+        if t < 0 {
+            q[j] -= 1;
+            let mut c = 0u128;
+            for i in 0..n {
+                let t = u[i + j] as u128 + v[i] as u128 + c;
+                u[i + j] = t as u64;
+                c = t >> 64;
+            }
+            u[j + n] = u[j + n].wrapping_add(c as u64);
+        }
+    }
+
+    // unnormalize remainder
+    let mut r = [0u64; L];
+    for i in 0..n {
+        r[i] = if s == 0 {
+            u[i]
+        } else {
+            (u[i] >> s) | (u[i + 1] << (64 - s))
+        };
+    }
+    (q, r)
+}
+
+fn mag_shr<const L: usize>(a: &[u64; L], k: usize) -> [u64; L] {
+    let (w, s) = (k / 64, k % 64);
+    let mut r = [0u64; L];
+    for i in 0..L.saturating_sub(w) {
+        let lo = a[i + w] >> s;
+        let hi = if s == 0 || i + w + 1 >= L {
+            0
+        } else {
+            a[i + w + 1] << (64 - s)
+        };
+        r[i] = lo | hi;
+    }
+    r
+}
+
+impl<const L: usize> From<u32> for FixInt<L> {
+    fn from(v: u32) -> FixInt<L> {
+        let mut mag = [0u64; L];
+        mag[0] = v as u64;
+        FixInt::new(false, mag)
+    }
+}
+
+impl<const L: usize> From<u64> for FixInt<L> {
+    fn from(v: u64) -> FixInt<L> {
+        let mut mag = [0u64; L];
+        mag[0] = v;
+        FixInt::new(false, mag)
+    }
+}
+
+impl<const L: usize> Ord for FixInt<L> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.neg, other.neg) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => mag_cmp(&self.mag, &other.mag),
+            (true, true) => mag_cmp(&other.mag, &self.mag),
+        }
+    }
+}
+
+impl<const L: usize> PartialOrd for FixInt<L> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const L: usize> fmt::Display for FixInt<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const D: u64 = 10_000_000_000_000_000_000; // 10^19
+        let mut chunks = Vec::new();
+        let mut m = self.mag;
+        loop {
+            let (q, r) = mag_divrem_small(&m, D);
+            chunks.push(r);
+            m = q;
+            if is_zero(&m) {
+                break;
+            }
+        }
+        let mut s = chunks.pop().unwrap().to_string();
+        for c in chunks.iter().rev() {
+            s += &format!("{:019}", c);
+        }
+        f.pad_integral(!self.neg, "", &s)
+    }
+}
+
+impl<const L: usize> fmt::LowerHex for FixInt<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = limbs(&self.mag).max(1);
+        let mut s = format!("{:x}", self.mag[n - 1]);
+        for i in (0..n - 1).rev() {
+            s += &format!("{:016x}", self.mag[i]);
+        }
+        f.pad_integral(!self.neg, "0x", &s)
+    }
+}
+
+impl<const L: usize> fmt::UpperHex for FixInt<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = limbs(&self.mag).max(1);
+        let mut s = format!("{:X}", self.mag[n - 1]);
+        for i in (0..n - 1).rev() {
+            s += &format!("{:016X}", self.mag[i]);
+        }
+        f.pad_integral(!self.neg, "0x", &s)
+    }
+}
+
+impl<'a, const L: usize> Add<&'a FixInt<L>> for &FixInt<L> {
+    type Output = FixInt<L>;
+
+    fn add(self, rhs: &'a FixInt<L>) -> FixInt<L> {
+        if self.neg == rhs.neg {
+            return FixInt::new(self.neg, mag_add(&self.mag, &rhs.mag));
+        }
+        match mag_cmp(&self.mag, &rhs.mag) {
+            Ordering::Less => FixInt::new(rhs.neg, mag_sub(&rhs.mag, &self.mag)),
+            _ => FixInt::new(self.neg, mag_sub(&self.mag, &rhs.mag)),
+        }
+    }
+}
+
+impl<'a, const L: usize> Sub<&'a FixInt<L>> for &FixInt<L> {
+    type Output = FixInt<L>;
+
+    fn sub(self, rhs: &'a FixInt<L>) -> FixInt<L> {
+        self + -*rhs
+    }
+}
+
+impl<'a, const L: usize> Mul<&'a FixInt<L>> for &FixInt<L> {
+    type Output = FixInt<L>;
+
+    fn mul(self, rhs: &'a FixInt<L>) -> FixInt<L> {
+        FixInt::new(self.neg != rhs.neg, mag_mul(&self.mag, &rhs.mag))
+    }
+}
+
+/// Truncated division (as BigInt and IBig)
+impl<'a, const L: usize> Div<&'a FixInt<L>> for &FixInt<L> {
+    type Output = FixInt<L>;
+
+    fn div(self, rhs: &'a FixInt<L>) -> FixInt<L> {
+        let (q, _) = mag_divrem(&self.mag, &rhs.mag);
+        FixInt::new(self.neg != rhs.neg, q)
+    }
+}
+
+/// Remainder with the sign of the dividend (as BigInt and IBig)
+impl<'a, const L: usize> Rem<&'a FixInt<L>> for &FixInt<L> {
+    type Output = FixInt<L>;
+
+    fn rem(self, rhs: &'a FixInt<L>) -> FixInt<L> {
+        let (_, r) = mag_divrem(&self.mag, &rhs.mag);
+        FixInt::new(self.neg, r)
+    }
+}
+
+macro_rules! forward_binop {
+    ($imp:ident, $method:ident) => {
+        impl<const L: usize> $imp<FixInt<L>> for FixInt<L> {
+            type Output = FixInt<L>;
+
+            #[inline]
+            fn $method(self, rhs: FixInt<L>) -> FixInt<L> {
+                (&self).$method(&rhs)
+            }
+        }
+
+        impl<'a, const L: usize> $imp<&'a FixInt<L>> for FixInt<L> {
+            type Output = FixInt<L>;
+
+            #[inline]
+            fn $method(self, rhs: &'a FixInt<L>) -> FixInt<L> {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl<const L: usize> $imp<FixInt<L>> for &FixInt<L> {
+            type Output = FixInt<L>;
+
+            #[inline]
+            fn $method(self, rhs: FixInt<L>) -> FixInt<L> {
+                self.$method(&rhs)
+            }
+        }
+    };
+}
+
+forward_binop!(Add, add);
+forward_binop!(Sub, sub);
+forward_binop!(Mul, mul);
+forward_binop!(Div, div);
+forward_binop!(Rem, rem);
+
+impl<const L: usize> Neg for FixInt<L> {
+    type Output = FixInt<L>;
+
+    fn neg(self) -> FixInt<L> {
+        FixInt::new(!self.neg, self.mag)
+    }
+}
+
+/// Arithmetic shift (floor, as BigInt)
+impl<const L: usize> Shr<usize> for FixInt<L> {
+    type Output = FixInt<L>;
+
+    fn shr(self, k: usize) -> FixInt<L> {
+        let r = FixInt::new(self.neg, mag_shr(&self.mag, k));
+        if self.neg && (0..k.min(64 * L)).any(|i| self.test_bit(i)) {
+            r - FixInt::one()
+        } else {
+            r
+        }
+    }
+}
+
+/// Bitwise and of non-negative values
+impl<const L: usize> BitAnd for FixInt<L> {
+    type Output = FixInt<L>;
+
+    fn bitand(self, rhs: FixInt<L>) -> FixInt<L> {
+        debug_assert!(!self.neg && !rhs.neg);
+        let mut mag = [0u64; L];
+        for (i, m) in mag.iter_mut().enumerate() {
+            *m = self.mag[i] & rhs.mag[i];
+        }
+        FixInt::new(false, mag)
+    }
+}
+
+impl<const L: usize> Zero for FixInt<L> {
+    fn zero() -> FixInt<L> {
+        FixInt::new(false, [0u64; L])
+    }
+
+    fn is_zero(&self) -> bool {
+        is_zero(&self.mag)
+    }
+}
+
+impl<const L: usize> One for FixInt<L> {
+    fn one() -> FixInt<L> {
+        FixInt::from(1u32)
+    }
+
+    fn is_one(&self) -> bool {
+        !self.neg && self.mag[0] == 1 && is_zero(&self.mag[1..])
+    }
+}
+
+impl<const L: usize> Number for FixInt<L> {
+    fn mod_cal(a: &Self, p: &Self) -> Self {
+        let x = a % p;
+        if x.neg {
+            x + p
+        } else {
+            x
+        }
+    }
+
+    fn pow(&self, exp: u32) -> Self {
+        let mut r = FixInt::one();
+        for i in (0..32 - exp.leading_zeros()).rev() {
+            r = r * r;
+            if exp >> i & 1 == 1 {
+                r = r * self;
+            }
+        }
+        r
+    }
+
+    fn mod_pow(a: &Self, e: &Self, m: &Self) -> Self {
+        let a = Self::mod_cal(a, m);
+        let mut r = FixInt::one() % m;
+        for i in (0..e.bit_len()).rev() {
+            r = r * r % m;
+            if e.test_bit(i) {
+                r = r * a % m;
+            }
+        }
+        r
+    }
+
+    fn exgcd(&self, b: &Self) -> (Self, Self, Self) {
+        let (mut x, mut y) = (*self, *b);
+        let (mut s0, mut s1) = (FixInt::one(), FixInt::zero());
+        let (mut t0, mut t1) = (FixInt::zero(), FixInt::one());
+        while y > FixInt::zero() {
+            let (q, r) = (x / y, x % y);
+            let s = s0 - q * s1;
+            let t = t0 - q * t1;
+            x = y;
+            y = r;
+            s0 = s1;
+            t0 = t1;
+            s1 = s;
+            t1 = t;
+        }
+        (x, s0, t0)
+    }
+
+    /// mod inverse n^(-1) mod p
+    fn mod_inv(a: &Self, p: &Self) -> Self {
+        let m = if a.neg { a + p } else { *a };
+        let (_, res, _) = m.exgcd(p);
+        if res.neg {
+            res + p
+        } else {
+            res
+        }
+    }
+
+    /// jacobi symbol: judge existing modsqrtmod (1: exist, -1: not exist)
+    fn jacobi(&self, q: &Self) -> i32 {
+        // binary algorithm (q odd, > 0)
+        let (mut a, mut n) = (Self::mod_cal(self, q), *q);
+        let mut t = 1;
+        while !a.is_zero() {
+            while !a.test_bit(0) {
+                a = a >> 1;
+                let r = n.mag[0] & 7;
+                if r == 3 || r == 5 {
+                    t = -t;
+                }
+            }
+            std::mem::swap(&mut a, &mut n);
+            if a.mag[0] & 3 == 3 && n.mag[0] & 3 == 3 {
+                t = -t;
+            }
+            a = a % n;
+        }
+        if n.is_one() {
+            t
+        } else {
+            0
+        }
+    }
+
+    fn to_hex(&self) -> String {
+        format!("{:x}", self)
+    }
+
+    fn add_ref(&self, rhs: &Self) -> Self {
+        self + rhs
+    }
+
+    fn sub_ref(&self, rhs: &Self) -> Self {
+        self - rhs
+    }
+
+    fn mul_ref(&self, rhs: &Self) -> Self {
+        self * rhs
+    }
+
+    fn gen_rand(min: &Self, max: &Self) -> Self {
+        let range = max - min;
+        assert!(range > FixInt::zero(), "empty range");
+        let bits = range.bit_len();
+        let n = bits.div_ceil(64);
+        let mut rng = rand::thread_rng();
+        loop {
+            let mut mag = [0u64; L];
+            for m in mag.iter_mut().take(n) {
+                *m = rng.gen();
+            }
+            if !bits.is_multiple_of(64) {
+                mag[n - 1] &= (1u64 << (bits % 64)) - 1;
+            }
+            let r = FixInt::new(false, mag);
+            if r < range {
+                return min + r;
+            }
+        }
+    }
+
+    fn bit_len(&self) -> usize {
+        let n = limbs(&self.mag);
+        if n == 0 {
+            0
+        } else {
+            64 * n - self.mag[n - 1].leading_zeros() as usize
+        }
+    }
+
+    fn test_bit(&self, bit: usize) -> bool {
+        bit < 64 * L && (self.mag[bit / 64] >> (bit % 64)) & 1 == 1
+    }
+
+    fn low_bits(&self, k: usize) -> Self {
+        let mut mag = [0u64; L];
+        let (w, b) = (k / 64, k % 64);
+        let w = if w < L {
+            mag[w] = self.mag[w] & ((1u64 << b) - 1);
+            w
+        } else {
+            L
+        };
+        mag[..w].copy_from_slice(&self.mag[..w]);
+        FixInt::new(false, mag)
+    }
+
+    fn max_bits() -> Option<usize> {
+        Some(64 * L)
+    }
+
+    /// Masked selection of sign and limbs
+    fn select(a: &Self, b: &Self, choice: bool) -> Self {
+        let m = u64::from(choice).wrapping_neg();
+        let mut mag = a.mag;
+        for (r, &y) in mag.iter_mut().zip(b.mag.iter()) {
+            *r ^= m & (*r ^ y);
+        }
+        FixInt {
+            neg: a.neg ^ (choice & (a.neg ^ b.neg)),
+            mag,
+        }
+    }
+
+    /// Folding on limbs (only the used limbs are touched)
+    fn mod_special(&self, k: usize, c: &Self, p: &Self) -> Self {
+        debug_assert!(!self.neg);
+        let (w, s) = (k / 64, k % 64);
+        let lc = limbs(&c.mag);
+        let mut t = self.mag;
+        let mut n = limbs(&t);
+        while n > w && 64 * n - t[n - 1].leading_zeros() as usize > k + 1 {
+            // hi = t >> k
+            let nh = n - w;
+            let mut hi = [0u64; L];
+            for i in 0..nh {
+                hi[i] = t[i + w] >> s;
+                if s != 0 && i + w + 1 < n {
+                    hi[i] |= t[i + w + 1] << (64 - s);
+                }
+            }
+            // t = t mod 2^k
+            t[w] &= (1u64 << s) - 1;
+            t[w + 1..n].fill(0);
+            // t += hi * c
+            for i in 0..limbs(&hi) {
+                let mut carry = 0u128;
+                for j in 0..lc {
+                    let v = hi[i] as u128 * c.mag[j] as u128 + t[i + j] as u128 + carry;
+                    t[i + j] = v as u64;
+                    carry = v >> 64;
+                }
+                let mut j = i + lc;
+                while carry != 0 {
+                    let v = t[j] as u128 + carry;
+                    t[j] = v as u64;
+                    carry = v >> 64;
+                    j += 1;
+                }
+            }
+            n = limbs(&t);
+        }
+        while mag_cmp(&t, &p.mag) != Ordering::Less {
+            t = mag_sub(&t, &p.mag);
+        }
+        FixInt::new(false, t)
+    }
+
+    fn from_bytes_radix(buf: &[u8], radix: u32) -> Self {
+        let (neg, digits) = match buf.first() {
+            Some(b'-') => (true, &buf[1..]),
+            _ => (false, buf),
+        };
+        assert!(!digits.is_empty(), "invalid digit");
+        let mut mag = [0u64; L];
+        for &c in digits {
+            let d = (c as char).to_digit(radix).expect("invalid digit");
+            mag = mag_mul_small(&mag, radix as u64, d as u64);
+        }
+        FixInt::new(neg, mag)
+    }
+
+    fn from_bytes_be(bytes: &[u8]) -> Self {
+        let nz = bytes.iter().position(|&c| c != 0).unwrap_or(bytes.len());
+        let bytes = &bytes[nz..];
+        assert!(bytes.len() <= 8 * L, "FixInt overflow");
+        let mut mag = [0u64; L];
+        for (i, &c) in bytes.iter().rev().enumerate() {
+            mag[i / 8] |= (c as u64) << (8 * (i % 8));
+        }
+        FixInt::new(false, mag)
+    }
+
+    fn to_bytes_be(&self) -> Vec<u8> {
+        let n = self.bit_len().div_ceil(8).max(1);
+        (0..n)
+            .rev()
+            .map(|i| (self.mag[i / 8] >> (8 * (i % 8))) as u8)
+            .collect()
+    }
+}
@@ -3,24 +3,41 @@
 //!
 //  Written by blanclux
 //  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+pub mod batch;
+pub mod brainpool;
+pub mod curve_param;
 pub mod ec_param;
 pub mod ecc_a;
+pub mod ecc_e;
 pub mod ecc_j;
+pub mod ecdh;
+pub mod ecdsa;
+pub mod ecies;
+pub mod ed_param;
+pub mod eddsa;
+pub mod field;
+pub mod fixint;
+pub mod montgomery;
 pub mod number;
+pub mod reduction;
+pub mod rfc6979;
+pub mod schnorr;
 
 use num_traits::{One, Zero};
 use number::Number;
-use std::fmt;
+use std::{error, fmt};
 
 #[derive(Clone, PartialEq, Copy)]
 pub enum EcAxis {
     Affine,
     Proj,
+    /// Edwards projective coordinates (X:Y:Z),  x = X/Z,  y = Y/Z
+    Edwards,
 }
 
 #[derive(Clone)]
 pub struct Point<T> {
-    pub axis: EcAxis, // Affine, Proj
+    pub axis: EcAxis, // Affine, Proj, Edwards
     pub x: T,
     pub y: T,
     pub z: T,
@@ -28,26 +45,104 @@ pub struct Point<T> {
 
 impl<T> Point<T>
 where
-    T: Zero + One,
+    T: Zero + One + PartialEq,
 {
     pub fn get_axis(&self) -> EcAxis {
         self.axis
     }
 
     pub fn is_zero(&self) -> bool {
-        if self.axis == EcAxis::Affine {
-            return self.x.is_zero() && self.y.is_zero();
+        match self.axis {
+            EcAxis::Affine => self.x.is_zero() && self.y.is_zero(),
+            EcAxis::Proj => self.z.is_zero(),
+            EcAxis::Edwards => self.x.is_zero() && self.y == self.z, // (0 : 1 : 1)
         }
-        self.z.is_zero() // Proj
     }
 
     pub fn set_zero(&mut self) {
         self.x.set_zero();
         self.y.set_zero();
         self.z.set_zero();
+        if self.axis == EcAxis::Edwards {
+            self.y.set_one();
+            self.z.set_one();
+        }
     }
 }
 
+/// Branch-free conditional copy of points (fixed-base table lookup)
+/// - constant time only for FixInt coordinates (see Number::select)
+pub(crate) trait Select {
+    /// p2 if choice else p1,  both are read whatever the choice (see Number::select)
+    fn select(p1: &Self, p2: &Self, choice: bool) -> Self;
+}
+
+impl<T: Number> Select for Point<T> {
+    fn select(p1: &Point<T>, p2: &Point<T>, choice: bool) -> Point<T> {
+        Point {
+            axis: p1.axis,
+            x: T::select(&p1.x, &p2.x, choice),
+            y: T::select(&p1.y, &p2.y, choice),
+            z: T::select(&p1.z, &p2.z, choice),
+        }
+    }
+}
+
+/// Point encoding format (SEC 1, 2.3.3)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PointFormat {
+    /// 0x02 / 0x03 || X
+    Compressed,
+    /// 0x04 || X || Y
+    Uncompressed,
+    /// 0x06 / 0x07 || X || Y
+    Hybrid,
+}
+
+/// Point validation / decoding errors
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EcError {
+    /// Unknown point encoding prefix
+    InvalidPrefix(u8),
+    /// Encoded length does not match the field size
+    InvalidLength(usize),
+    /// Coordinate is not in [0, p)
+    CoordinateOutOfRange,
+    /// x^3 + ax + b has no square root (no point at x)
+    NotQuadraticResidue,
+    /// Point does not satisfy the curve equation
+    NotOnCurve,
+    /// Point is not in the prime-order subgroup
+    NotInSubgroup,
+    /// Point at infinity where a finite point is required
+    PointAtInfinity,
+    /// Hybrid encoding prefix does not match the parity of y
+    HybridParity,
+    /// Private scalar is not in [1, n - 1]
+    InvalidScalar,
+    /// Signature component not in [1, n - 1] or invalid recovery id
+    InvalidSignature,
+}
+
+impl fmt::Display for EcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EcError::InvalidPrefix(b) => write!(f, "Invalid point compression: {:#04x}", b),
+            EcError::InvalidLength(n) => write!(f, "Invalid encoded point length: {}", n),
+            EcError::CoordinateOutOfRange => write!(f, "Coordinate out of range"),
+            EcError::NotQuadraticResidue => write!(f, "Not a quadratic residue"),
+            EcError::NotOnCurve => write!(f, "Point not on curve"),
+            EcError::NotInSubgroup => write!(f, "Point not in prime-order subgroup"),
+            EcError::PointAtInfinity => write!(f, "Point at infinity"),
+            EcError::HybridParity => write!(f, "Hybrid encoding parity mismatch"),
+            EcError::InvalidScalar => write!(f, "Invalid private scalar"),
+            EcError::InvalidSignature => write!(f, "Invalid signature"),
+        }
+    }
+}
+
+impl error::Error for EcError {}
+
 pub trait PointOp<T> {
     fn set(&mut self, other: &Point<T>);
     fn equals(&self, other: &Point<T>) -> bool;
@@ -68,10 +163,10 @@ impl<T: Number> PointOp<T> for Point<T> {
 
 impl<T: Number> fmt::Display for Point<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.axis == EcAxis::Affine {
-            return write!(f, "[{}, {}]", self.x, self.y);
+        match self.axis {
+            EcAxis::Affine => write!(f, "[{}, {}]", self.x, self.y),
+            EcAxis::Proj | EcAxis::Edwards => write!(f, "[{}, {}, {}]", self.x, self.y, self.z),
         }
-        write!(f, "[{}, {}, {}]", self.x, self.y, self.z)
     }
 }
 
@@ -80,12 +175,100 @@ pub trait EcOp<T: Number> {
     fn on_curve(&self, p: &Point<T>) -> bool;
     /// Check equality
     fn equals(&self, p1: &Point<T>, p2: &Point<T>) -> bool;
-    /// Find points on curve at x
-    fn point_from_x(&self, x: &T, yt: u32) -> Point<T>;
+    /// Find the point on curve at x with y = yt mod 2 (None if x^3 + ax + b is not a square)
+    fn point_from_x(&self, x: &T, yt: u32) -> Option<Point<T>>;
     /// Find points on curve at (x, y)
     fn point_from_xy(&self, x: &T, y: &T) -> Point<T>;
-    /// OS2ECPP: Decode a point
-    fn decode_point(&self, enc: &[u8]) -> Point<T>;
+    /// OS2ECPP: Decode a point (panics on invalid encoding)
+    fn decode_point(&self, enc: &[u8]) -> Point<T> {
+        match self.try_decode_point(enc) {
+            Ok(p) => p,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// OS2ECPP: Decode and validate a point (SEC 1, 2.3.4)
+    fn try_decode_point(&self, enc: &[u8]) -> Result<Point<T>, EcError> {
+        let pt = self.try_decode_point_partial(enc)?;
+        self.check_order(&pt)?;
+        Ok(pt)
+    }
+
+    /// OS2ECPP: Decode a point on the curve (no subgroup check)
+    fn try_decode_point_partial(&self, enc: &[u8]) -> Result<Point<T>, EcError> {
+        let p = self.get_prime();
+        let ln = self.get_field_len();
+
+        if enc.is_empty() {
+            return Err(EcError::InvalidLength(0));
+        }
+        let pt = match enc[0] {
+            // point at infinity
+            0x00 => {
+                if enc.len() != 1 {
+                    return Err(EcError::InvalidLength(enc.len()));
+                }
+                return Ok(self.get_zero());
+            }
+            // compressed
+            0x02 | 0x03 => {
+                if enc.len() != ln + 1 {
+                    return Err(EcError::InvalidLength(enc.len()));
+                }
+                let x = T::from_bytes_be(&enc[1..]);
+                if x >= p {
+                    return Err(EcError::CoordinateOutOfRange);
+                }
+                self.point_from_x(&x, (enc[0] & 1).into())
+                    .ok_or(EcError::NotQuadraticResidue)?
+            }
+            // uncompressed / hybrid
+            0x04 | 0x06 | 0x07 => {
+                if enc.len() != 2 * ln + 1 {
+                    return Err(EcError::InvalidLength(enc.len()));
+                }
+                let x = T::from_bytes_be(&enc[1..(ln + 1)]);
+                let y = T::from_bytes_be(&enc[(ln + 1)..]);
+                if enc[0] != 0x04 && y.test_bit(0) != (enc[0] & 1 == 1) {
+                    return Err(EcError::HybridParity);
+                }
+                self.point_from_xy(&x, &y)
+            }
+            b => return Err(EcError::InvalidPrefix(b)),
+        };
+        self.check_point_partial(&pt)?;
+        Ok(pt)
+    }
+
+    /// Public key validation: coordinates in range, on curve, in subgroup
+    fn check_point(&self, p_p: &Point<T>) -> Result<(), EcError> {
+        self.check_point_partial(p_p)?;
+        self.check_order(p_p)
+    }
+
+    /// Partial public key validation: coordinates in range, on curve
+    fn check_point_partial(&self, p_p: &Point<T>) -> Result<(), EcError> {
+        if self.is_zero(p_p) {
+            return Ok(());
+        }
+        let p = self.get_prime();
+        let q = self.to_affine(p_p);
+        if q.x < T::zero() || q.x >= p || q.y < T::zero() || q.y >= p {
+            return Err(EcError::CoordinateOutOfRange);
+        }
+        if !self.on_curve(&q) {
+            return Err(EcError::NotOnCurve);
+        }
+        Ok(())
+    }
+
+    /// Subgroup check: n * P = O (always true for h = 1)
+    fn check_order(&self, p_p: &Point<T>) -> Result<(), EcError> {
+        if !self.get_cofac().is_one() && !self.is_zero(&self.mul(p_p, &self.get_order())) {
+            return Err(EcError::NotInSubgroup);
+        }
+        Ok(())
+    }
     /// Convert 3D to 2D
     fn to_affine(&self, p: &Point<T>) -> Point<T>;
     fn normalize(&self, p: &mut Point<T>);
@@ -112,44 +295,35 @@ pub trait EcOp<T: Number> {
     /// Multiply (k * P)
     /// Sliding window multiplication
     fn mul(&self, p_p: &Point<T>, k: &T) -> Point<T> {
-        let w = get_win_size(k);
-        let table = self.get_win_table(p_p, w);
-        let mut rv = self.get_zero();
-
-        if k.is_zero() {
-            return rv;
-        }
+        mul_window(
+            p_p,
+            k,
+            self.get_zero(),
+            |p1, p2| self.add(p1, p2),
+            |p| self.double(p),
+        )
+    }
 
-        let mut word = 0;
-        let mut wbits = 0;
-        let size = k.bit_len() - 1;
-        let mut bit;
+    /// Multiply (k * G) by the generator (for secret k, like mul_ct)
+    /// EcpA / EcpJ use a fixed-base table of G built on first use
+    fn mul_base(&self, k: &T) -> Point<T> {
+        self.mul_ct(&self.get_gp(), k)
+    }
 
-        let mut inw = 0;
-        for s in (0..size + 1).rev() {
-            rv = self.double(&rv);
-            bit = k.test_bit(s);
-            if inw == 0 && !bit {
-                continue;
-            }
-            if inw == 0 {
-                inw = 1;
-                word = 1;
-                wbits = 1;
-            } else {
-                if bit {
-                    word = (word << 1) + 1;
-                } else {
-                    word <<= 1;
-                }
-                wbits += 1;
-            }
-            if wbits == w || s == 0 {
-                rv = self.add(&rv, &table[word]);
-                inw = 0;
-            }
-        }
-        rv
+    /// Multiply (k * P) (Montgomery ladder)
+    /// One add and one double per bit, k padded to max(bitlen(n), bitlen(k)) bits
+    /// (the point operation sequence does not depend on k; the underlying
+    ///  big number arithmetic is not constant time)
+    fn mul_ct(&self, p_p: &Point<T>, k: &T) -> Point<T> {
+        let bits = self.get_order().bit_len().max(k.bit_len());
+        mul_ladder(
+            p_p,
+            k,
+            bits,
+            self.get_zero(),
+            |p1, p2| self.add(p1, p2),
+            |p| self.double(p),
+        )
     }
 
     /// Builds w-bit lookup window
@@ -168,6 +342,52 @@ pub trait EcOp<T: Number> {
         lookup
     }
 
+    /// Builds the odd multiples table of w-bit NAF:  table[i] = (2i + 1) * P,  0 <= i < 2^(w-2)
+    fn get_wnaf_table(&self, p: &Point<T>, w: usize) -> Vec<Point<T>> {
+        if w < 2 {
+            return vec![]; // no window
+        }
+        odd_multiples(p, w, |p1, p2| self.add(p1, p2), |p| self.double(p))
+    }
+
+    /// Multiply (k * P) (width-w NAF, odd multiples table of get_wnaf_table)
+    fn mul_wnaf(&self, p_p: &Point<T>, k: &T) -> Point<T> {
+        let w = get_wnaf_size(k);
+        mul_wnaf(
+            &self.get_wnaf_table(p_p, w),
+            k,
+            w,
+            self.get_zero(),
+            |p1, p2| self.add(p1, p2),
+            |p| self.double(p),
+            |p| self.negate(p),
+        )
+    }
+
+    /// Multiply and add (k * P + l * Q)
+    /// Interleaved wNAF (Shamir's trick):  the doublings are shared by both scalars
+    fn mul2(&self, p_p: &Point<T>, k: &T, p_q: &Point<T>, l: &T) -> Point<T> {
+        mul_multi_wnaf(
+            &[(p_p, k), (p_q, l)],
+            self.get_zero(),
+            |p1, p2| self.add(p1, p2),
+            |p| self.double(p),
+            |p| self.negate(p),
+        )
+    }
+
+    /// Multi-scalar multiplication (k_1 * P_1 + .. + k_m * P_m)
+    /// Interleaved wNAF:  one doubling per bit of max(k_i) for all terms
+    fn mul_multi(&self, terms: &[(&Point<T>, &T)]) -> Point<T> {
+        mul_multi_wnaf(
+            terms,
+            self.get_zero(),
+            |p1, p2| self.add(p1, p2),
+            |p| self.double(p),
+            |p| self.negate(p),
+        )
+    }
+
     /// Multiply (k * P) (Binary method)
     fn mul_bin(&self, p: &Point<T>, k: &T) -> Point<T> {
         let mut p_r = self.get_zero();
@@ -189,20 +409,58 @@ pub trait EcOp<T: Number> {
     fn gen_point(&self) -> Point<T>;
     /// Order calculation
     fn calc_order(&self, p: &Point<T>) -> T;
+    /// Get prime p
+    fn get_prime(&self) -> T;
+    /// Get coefficient a
+    fn get_a(&self) -> T;
+    /// Field element length in bytes: ceil(bitlen(p) / 8)
+    fn get_field_len(&self) -> usize {
+        self.get_prime().bit_len().div_ceil(8)
+    }
     /// Get order
     fn get_order(&self) -> T;
     /// Get cofactor
     fn get_cofac(&self) -> T;
     /// Get generator point
     fn get_gp(&self) -> Point<T>;
-    fn get_encoded(&self, p: &Point<T>) -> Vec<u8>;
+    /// EC2OSP: Encode a point in compressed form
+    fn get_encoded(&self, p: &Point<T>) -> Vec<u8> {
+        self.encode_point(p, PointFormat::Compressed)
+    }
+    /// EC2OSP: Encode a point (SEC 1, 2.3.3),  X and Y are field length octets
+    fn encode_point(&self, p: &Point<T>, format: PointFormat) -> Vec<u8> {
+        if self.is_zero(p) {
+            return vec![0x00];
+        }
+        let q = self.to_affine(p);
+        let ln = self.get_field_len();
+        let yt = u8::from(q.y.test_bit(0));
+
+        let mut po = vec![match format {
+            PointFormat::Compressed => 0x02 | yt,
+            PointFormat::Uncompressed => 0x04,
+            PointFormat::Hybrid => 0x06 | yt,
+        }];
+        po.extend_from_slice(&q.x.to_bytes_be_pad(ln));
+        if format != PointFormat::Compressed {
+            po.extend_from_slice(&q.y.to_bytes_be_pad(ln));
+        }
+        po
+    }
     /// Display point data
     fn print(&self, s: &str, p: &Point<T>);
 }
 
+/// Short Weierstrass curves (y^2 = x^3 + ax + b):  EcpA, EcpJ
+/// - twisted Edwards curves (EcpE) have no coefficient b and do not implement it
+pub trait WeierstrassOp<T: Number>: EcOp<T> {
+    /// Get coefficient b
+    fn get_b(&self) -> T;
+}
+
 /// Gets lookup window size
 pub fn get_win_size<T: Number>(n: &T) -> usize {
-    let table = vec![47, 157, 474, 1324, 3529, 9065];
+    let table = [47, 157, 474, 1324, 3529, 9065];
 
     let pbits = n.bit_len();
     for ret in (0..6).rev() {
@@ -213,3 +471,276 @@ pub fn get_win_size<T: Number>(n: &T) -> usize {
     }
     2
 }
+
+/// Gets width of NAF window (wNAF)
+/// - w + 1 is better than w when bitlen(k) > 2^(w-2) * (w+1) * (w+2)
+///   (2^(w-2) table additions, bitlen(k) / (w+1) additions)
+pub fn get_wnaf_size<T: Number>(k: &T) -> usize {
+    let table = [12, 40, 120, 336, 896, 2304];
+
+    let kbits = k.bit_len();
+    for ret in (0..6).rev() {
+        if kbits > table[ret] {
+            return ret + 3;
+        }
+    }
+    2
+}
+
+/// Width-w NAF of k >= 0 (least significant digit first)
+/// - digits are 0 or odd with |d| < 2^(w-1),  at most one non-zero digit in w consecutive digits
+pub fn wnaf<T: Number>(k: &T, w: usize) -> Vec<i32> {
+    assert!((2..=16).contains(&w), "invalid NAF width");
+    let len = k.bit_len();
+    let mut naf = vec![0; len + 1];
+    let mut carry = 0;
+    let mut bit = 0;
+
+    // scan the bits of k + carry,  an odd window becomes a signed digit
+    while bit < len {
+        if i32::from(k.test_bit(bit)) == carry {
+            bit += 1;
+            continue;
+        }
+        let now = w.min(len - bit);
+        let mut word = (0..now).fold(carry, |u, b| u + (i32::from(k.test_bit(bit + b)) << b));
+        carry = (word >> (w - 1)) & 1;
+        word -= carry << w;
+        naf[bit] = word;
+        bit += now;
+    }
+    naf[len] = carry;
+    while naf.last() == Some(&0) {
+        naf.pop();
+    }
+    naf
+}
+
+/// Width-w NAF multiplication with the odd multiples table of P and the given point add / double / negate
+pub(crate) fn mul_wnaf<P: Clone, T: Number>(
+    table: &[P],
+    k: &T,
+    w: usize,
+    zero: P,
+    add: impl Fn(&P, &P) -> P,
+    double: impl Fn(&P) -> P,
+    negate: impl Fn(&P) -> P,
+) -> P {
+    let mut rv = zero;
+
+    for &d in wnaf(k, w).iter().rev() {
+        rv = double(&rv);
+        if d > 0 {
+            rv = add(&rv, &table[(d >> 1) as usize]);
+        } else if d < 0 {
+            rv = add(&rv, &negate(&table[(-d >> 1) as usize]));
+        }
+    }
+    rv
+}
+
+/// Interleaved wNAF multiplication (k_1 * P_1 + .. + k_m * P_m) with the given point add / double / negate
+/// - one doubling per bit of max(k_i) (Straus / Shamir's trick),  each scalar with its own window
+pub(crate) fn mul_multi_wnaf<P: Clone, T: Number>(
+    terms: &[(&P, &T)],
+    zero: P,
+    add: impl Fn(&P, &P) -> P,
+    double: impl Fn(&P) -> P,
+    negate: impl Fn(&P) -> P,
+) -> P {
+    let digits: Vec<(Vec<i32>, Vec<P>)> = terms
+        .iter()
+        .map(|&(p_p, k)| {
+            let w = get_wnaf_size(k);
+            (wnaf(k, w), odd_multiples(p_p, w, &add, &double))
+        })
+        .collect();
+    let len = digits.iter().map(|(naf, _)| naf.len()).max().unwrap_or(0);
+    let mut rv = zero;
+
+    for i in (0..len).rev() {
+        rv = double(&rv);
+        for (naf, table) in digits.iter() {
+            match naf.get(i) {
+                Some(&d) if d > 0 => rv = add(&rv, &table[(d >> 1) as usize]),
+                Some(&d) if d < 0 => rv = add(&rv, &negate(&table[(-d >> 1) as usize])),
+                _ => {}
+            }
+        }
+    }
+    rv
+}
+
+/// Odd multiples of P for width-w NAF:  table[i] = (2i + 1) * P,  0 <= i < 2^(w-2)
+pub(crate) fn odd_multiples<P: Clone>(
+    p_p: &P,
+    w: usize,
+    add: impl Fn(&P, &P) -> P,
+    double: impl Fn(&P) -> P,
+) -> Vec<P> {
+    let mut table = Vec::with_capacity(1 << (w - 2));
+    table.push(p_p.clone());
+    if w > 2 {
+        let p2 = double(p_p);
+        for i in 1..(1 << (w - 2)) {
+            table.push(add(&p2, &table[i - 1]));
+        }
+    }
+    table
+}
+
+/// Sliding window multiplication with the given point add / double
+pub(crate) fn mul_window<P: Clone, T: Number>(
+    p_p: &P,
+    k: &T,
+    zero: P,
+    add: impl Fn(&P, &P) -> P,
+    double: impl Fn(&P) -> P,
+) -> P {
+    if k.is_zero() {
+        return zero;
+    }
+    // 2^w lookup table:  table[i] = i * P
+    let w = get_win_size(k);
+    let mut table = Vec::with_capacity(1 << w);
+    table.push(zero.clone());
+    for i in 1..(1 << w) {
+        table.push(add(p_p, &table[i - 1]));
+    }
+    let mut rv = zero;
+
+    let mut word = 0;
+    let mut wbits = 0;
+    let size = k.bit_len() - 1;
+    let mut bit;
+
+    let mut inw = 0;
+    for s in (0..size + 1).rev() {
+        rv = double(&rv);
+        bit = k.test_bit(s);
+        if inw == 0 && !bit {
+            continue;
+        }
+        if inw == 0 {
+            inw = 1;
+            word = 1;
+            wbits = 1;
+        } else {
+            if bit {
+                word = (word << 1) + 1;
+            } else {
+                word <<= 1;
+            }
+            wbits += 1;
+        }
+        if wbits == w || s == 0 {
+            rv = add(&rv, &table[word]);
+            inw = 0;
+        }
+    }
+    rv
+}
+
+/// Montgomery ladder over bits (k padded) with the given point add / double
+pub(crate) fn mul_ladder<P: Clone, T: Number>(
+    p_p: &P,
+    k: &T,
+    bits: usize,
+    zero: P,
+    add: impl Fn(&P, &P) -> P,
+    double: impl Fn(&P) -> P,
+) -> P {
+    // R[0] = O,  R[1] = P  (invariant: R[1] - R[0] = P)
+    let mut r = [zero, p_p.clone()];
+
+    for s in (0..bits).rev() {
+        let b = usize::from(k.test_bit(s));
+        r[1 - b] = add(&r[0], &r[1]);
+        r[b] = double(&r[b]);
+    }
+    let [rv, _] = r;
+    rv
+}
+
+/// Window width of the fixed-base table
+pub(crate) const BASE_WIN: usize = 5;
+
+/// Fixed-base table (see fixed_base_table)
+pub(crate) type BaseTable<P> = Vec<Vec<P>>;
+
+/// Fixed-base table of G for scalars of up to bits bits (w = BASE_WIN)
+/// - table[i][j] = (2j + 1) * 2^(w*i) * G  (0 <= i <= ceil(bits / w),  0 <= j < 2^(w-1))
+pub(crate) fn fixed_base_table<P: Clone>(
+    p_g: &P,
+    bits: usize,
+    add: impl Fn(&P, &P) -> P,
+    double: impl Fn(&P) -> P,
+) -> BaseTable<P> {
+    let w = BASE_WIN;
+    let rows = bits.div_ceil(w) + 1;
+    let mut table = Vec::with_capacity(rows);
+
+    // b = 2^(w*i) * G
+    let mut b = p_g.clone();
+    for _ in 0..rows {
+        let b2 = double(&b);
+        let mut row: Vec<P> = Vec::with_capacity(1 << (w - 1));
+        row.push(b);
+        for j in 1..(1 << (w - 1)) {
+            row.push(add(&row[j - 1], &b2));
+        }
+        // 2^w * b = 2^(w-1) * (2 * b)
+        b = b2;
+        for _ in 1..w {
+            b = double(&b);
+        }
+        table.push(row);
+    }
+    table
+}
+
+/// Fixed-base multiplication (k * G) with a table of fixed_base_table
+/// - 0 <= k < n,  n odd,  table built for bitlen(n) bits
+/// - k is recoded in signed odd digits (k = sum d_i * 2^(w*i),  d_i odd,  |d_i| < 2^w),
+///   so there is one table addition per row whatever the value of k
+/// - every entry of a row is read and the digit is taken with Select;  the sign of
+///   the digit and k even (computed as -((n - k) * G)) are applied with select too
+///   (select is constant time only for FixInt;  the big number arithmetic is not constant time)
+pub(crate) fn mul_fixed_base<P: Clone + Select, T: Number>(
+    table: &BaseTable<P>,
+    k: &T,
+    n: &T,
+    add: impl Fn(&P, &P) -> P,
+    negate: impl Fn(&P) -> P,
+) -> P {
+    let w = BASE_WIN;
+    let even = !k.test_bit(0);
+    let mut e = T::select(k, &n.sub_ref(k), even);
+
+    let mut rv = None;
+    for (i, row) in table.iter().enumerate() {
+        // d = (e mod 2^(w+1)) - 2^w  (last row: e itself, 0 < e < 2^w),  e = (e - d) / 2^w
+        let u = (0..=w).fold(0i32, |u, b| u | (i32::from(e.test_bit(b)) << b));
+        let d = if i + 1 == table.len() {
+            u
+        } else {
+            u - (1 << w)
+        };
+        e = (e + T::from(1u32 << w) - T::from(u as u32)) >> w;
+
+        // d_i * 2^(w*i) * G:  |d| and the sign of d without a branch
+        let s = d >> 31;
+        let j = (((d ^ s) - s) >> 1) as usize;
+        let mut p = row[0].clone();
+        for (l, q) in row.iter().enumerate().skip(1) {
+            p = P::select(&p, q, l == j);
+        }
+        let p = P::select(&p, &negate(&p), s != 0);
+        rv = Some(match rv {
+            None => p,
+            Some(r) => add(&r, &p),
+        });
+    }
+    let rv = rv.unwrap();
+    P::select(&rv, &negate(&rv), even)
+}
@@ -0,0 +1,98 @@
+//!
+//! Montgomery Modular Arithmetic
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+use super::number::{add_mod, half_mod, sub_mod, Number};
+
+///   Montgomery domain of an odd modulus p,  R = 2^k > p  (64 | k)
+///   Field elements are kept as x * R mod p in [0, p)
+#[derive(Clone, Debug)]
+pub struct Montgomery<T: Number> {
+    p: T,
+    k: usize,
+    /// R^2 mod p
+    r2: T,
+    /// -p^(-1) mod R
+    pinv: T,
+    /// R mod p (1 in Montgomery form)
+    one: T,
+}
+
+impl<T: Number> Montgomery<T> {
+    pub fn new(p: &T) -> Montgomery<T> {
+        assert!(p.test_bit(0), "modulus must be odd");
+        // R = 2^k,  k: multiple of 64 (limb aligned mask and shift)
+        let k = p.bit_len().div_ceil(64) * 64;
+        let r = T::from(2u32).pow(k as u32);
+        let pinv = r.sub_ref(&Number::mod_inv(p, &r));
+        Montgomery {
+            p: p.clone(),
+            k,
+            r2: r.mul_ref(&r) % p,
+            pinv,
+            one: r % p,
+        }
+    }
+
+    /// Get modulus p
+    pub fn get_prime(&self) -> &T {
+        &self.p
+    }
+
+    /// 1 in Montgomery form (R mod p)
+    pub fn one(&self) -> &T {
+        &self.one
+    }
+
+    /// x -> x * R mod p
+    pub fn to_mont(&self, x: &T) -> T {
+        self.redc(Number::mod_cal(x, &self.p) * &self.r2)
+    }
+
+    /// x * R -> x mod p
+    pub fn from_mont(&self, x: &T) -> T {
+        self.redc(x.clone())
+    }
+
+    /// REDC:  t * R^(-1) mod p  (0 <= t < p * R)
+    pub fn redc(&self, t: T) -> T {
+        let m = t.low_bits(self.k).mul_ref(&self.pinv).low_bits(self.k);
+        let u = (t + m * &self.p) >> self.k;
+        if u >= self.p {
+            u - &self.p
+        } else {
+            u
+        }
+    }
+
+    /// a * b * R^(-1) mod p
+    #[inline]
+    pub fn mul(&self, a: &T, b: &T) -> T {
+        self.redc(a.mul_ref(b))
+    }
+
+    /// a^2 * R^(-1) mod p
+    #[inline]
+    pub fn sqr(&self, a: &T) -> T {
+        self.redc(a.mul_ref(a))
+    }
+
+    /// a + b mod p
+    #[inline]
+    pub fn add(&self, a: &T, b: &T) -> T {
+        add_mod(a, b, &self.p)
+    }
+
+    /// a - b mod p
+    #[inline]
+    pub fn sub(&self, a: &T, b: &T) -> T {
+        sub_mod(a, b, &self.p)
+    }
+
+    /// a / 2 mod p
+    #[inline]
+    pub fn half(&self, a: &T) -> T {
+        half_mod(a, &self.p)
+    }
+}
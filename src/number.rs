@@ -42,7 +42,6 @@ where
     fn exgcd(&self, b: &Self) -> (Self, Self, Self);
     fn mod_inv(a: &Self, p: &Self) -> Self;
     fn jacobi(&self, q: &Self) -> i32;
-    fn mod_sqrt(a: &Self, p: &Self) -> Self;
     fn to_hex(&self) -> String;
     fn add_ref(&self, rhs: &Self) -> Self;
     fn sub_ref(&self, rhs: &Self) -> Self;
@@ -50,9 +49,153 @@ where
     fn gen_rand(min: &Self, max: &Self) -> Self;
     fn bit_len(&self) -> usize;
     fn test_bit(&self, bit: usize) -> bool;
+    /// self mod 2^k (self >= 0)
+    fn low_bits(&self, k: usize) -> Self;
     fn from_bytes_radix(buf: &[u8], radix: u32) -> Self; // b"..."
     fn from_bytes_be(bytes: &[u8]) -> Self;
     fn to_bytes_be(&self) -> Vec<u8>;
+    /// Big-endian bytes left-padded with zeros to len (I2OSP)
+    /// - panics if the value does not fit in len bytes
+    fn to_bytes_be_pad(&self, len: usize) -> Vec<u8> {
+        let b = self.to_bytes_be();
+        let nz = b.iter().position(|&c| c != 0).unwrap_or(b.len());
+        let b = &b[nz..];
+        assert!(b.len() <= len, "integer too large for {} bytes", len);
+        let mut buf = vec![0u8; len];
+        buf[len - b.len()..].copy_from_slice(b);
+        buf
+    }
+    /// Largest bit length of a value (None: unbounded,  FixInt: 64 * L)
+    fn max_bits() -> Option<usize> {
+        None
+    }
+    /// b if choice else a,  without a branch on choice
+    /// - a + (b - a) * choice:  for BigInt / IBig this is a big number multiply whose
+    ///   time depends on the values (not constant time)
+    /// - FixInt selects the limbs with a mask (constant time)
+    fn select(a: &Self, b: &Self, choice: bool) -> Self {
+        a.add_ref(&(b.sub_ref(a) * Self::from(u32::from(choice))))
+    }
+    /// Square root mod an odd prime p,  None if a is not a quadratic residue
+    /// - p = 3 mod 4: a^((p+1)/4),  p = 5 mod 8: Atkin,  p = 1 mod 8: Tonelli-Shanks
+    fn mod_sqrt(a: &Self, p: &Self) -> Option<Self> {
+        let one = Self::one();
+        let a = Self::mod_cal(a, p);
+        if a.is_zero() {
+            return Some(a);
+        }
+        let r = if p.test_bit(1) {
+            // p = 3 mod 4
+            Self::mod_pow(&a, &(p.add_ref(&one) >> 2), p)
+        } else if p.test_bit(2) {
+            // p = 5 mod 8 (Atkin):  b = (2a)^((p-5)/8),  i = 2ab^2,  r = ab(i - 1)
+            let a2 = a.add_ref(&a) % p;
+            let b = Self::mod_pow(&a2, &(p.clone() >> 3), p);
+            let i = a2 * &b % p * &b % p;
+            a.mul_ref(&b) % p * Self::mod_cal(&(i - one), p) % p
+        } else {
+            tonelli_shanks(&a, p)?
+        };
+        if r.mul_ref(&r) % p == a {
+            Some(r)
+        } else {
+            None
+        }
+    }
+    /// self mod p for a special-form prime p = 2^k - c  (self >= 0, c < 2^(k/2))
+    /// - folding:  t_hi * 2^k + t_lo = t_hi * c + t_lo (mod p)
+    fn mod_special(&self, k: usize, c: &Self, p: &Self) -> Self {
+        let mut t = self.clone();
+        // t < 2^(k+1) < 2p + 2c after folding
+        while t.bit_len() > k + 1 {
+            let lo = t.low_bits(k);
+            let hi = t >> k;
+            t = if c.is_one() { hi + lo } else { hi * c + lo };
+        }
+        while &t >= p {
+            t = t - p;
+        }
+        t
+    }
+}
+
+/// The number type holds the values of the field arithmetic mod p
+/// - unreduced products of three field elements plus the additions:  3k + 1 limbs
+///   for a field of k 64-bit limbs (see FixInt)
+pub fn fits_field<T: Number>(p: &T) -> bool {
+    T::max_bits().is_none_or(|m| 64 * (3 * p.bit_len().div_ceil(64) + 1) <= m)
+}
+
+/// a + b mod p  (0 <= a, b < p)
+#[inline]
+pub(crate) fn add_mod<T: Number>(a: &T, b: &T, p: &T) -> T {
+    let r = a.add_ref(b);
+    if &r >= p {
+        r - p
+    } else {
+        r
+    }
+}
+
+/// a - b mod p  (0 <= a, b < p)
+#[inline]
+pub(crate) fn sub_mod<T: Number>(a: &T, b: &T, p: &T) -> T {
+    if a >= b {
+        a.sub_ref(b)
+    } else {
+        a.add_ref(p) - b
+    }
+}
+
+/// a / 2 mod p  (0 <= a < p,  p odd)
+#[inline]
+pub(crate) fn half_mod<T: Number>(a: &T, p: &T) -> T {
+    if a.test_bit(0) {
+        a.add_ref(p) >> 1
+    } else {
+        a.clone() >> 1
+    }
+}
+
+/// Tonelli-Shanks (p = 1 mod 8),  None if a is not a quadratic residue
+#[allow(clippy::many_single_char_names)]
+fn tonelli_shanks<T: Number>(a: &T, p: &T) -> Option<T> {
+    let one = T::one();
+    // p - 1 = q * 2^s,  q is odd
+    let p1 = p.sub_ref(&one);
+    let s = (0..).find(|&i| p1.test_bit(i)).unwrap();
+    let q = p1.clone() >> s;
+
+    // z: the smallest quadratic non-residue
+    let mut z = T::from(2u32);
+    while z.jacobi(p) != -1 {
+        z = z + &one;
+    }
+    let mut m = s;
+    let mut c = T::mod_pow(&z, &q, p);
+    let mut t = T::mod_pow(a, &q, p);
+    let mut r = T::mod_pow(a, &(q.add_ref(&one) >> 1), p);
+    while !t.is_one() {
+        // least i (0 < i < m):  t^(2^i) = 1
+        let mut i = 0;
+        let mut t2 = t.clone();
+        while !t2.is_one() {
+            t2 = t2.mul_ref(&t2) % p;
+            i += 1;
+            if i == m {
+                return None;
+            }
+        }
+        let mut b = c;
+        for _ in 0..m - i - 1 {
+            b = b.mul_ref(&b) % p;
+        }
+        m = i;
+        c = b.mul_ref(&b) % p;
+        t = t * &c % p;
+        r = r * &b % p;
+    }
+    Some(r)
 }
 
 impl Number for IBig {
@@ -68,16 +211,14 @@ impl Number for IBig {
         self.pow(exp as usize)
     }
 
+    /// Right-to-left binary method,  result in [0, m)
     fn mod_pow(a: &Self, e: &IBig, m: &IBig) -> IBig {
-        if e.is_zero() {
-            return ibig!(1);
-        }
         let mut ex = e.clone();
-        let mut res = ibig!(1);
-        let mut t = a % m;
+        let mut res = ibig!(1) % m;
+        let mut t = Self::mod_cal(a, m);
         while ex > ibig!(0) {
-            if e & 1 == ibig!(1) {
-                res = res * a % m;
+            if &ex & ibig!(1) == ibig!(1) {
+                res = res * &t % m;
             }
             t = &t * &t % m;
             ex >>= 1;
@@ -145,44 +286,6 @@ impl Number for IBig {
         }
     }
 
-    /// Tonelli's Algorithm
-    #[allow(clippy::many_single_char_names)]
-    fn mod_sqrt(a: &Self, p: &Self) -> IBig {
-        let one = ibig!(1);
-        let p1 = p - 1i32;
-
-        // b: some non-quadratic-residue
-        let mut b = ibig!(0);
-        while b.is_zero() || b.jacobi(p) != -1 {
-            b = Number::gen_rand(&one, &p1);
-        }
-        // p = t * 2^s + 1, t is odd
-        let mut t = p1;
-        let mut s = IBig::zero();
-        while &t & IBig::one() == IBig::zero() {
-            t = &t >> 1;
-            s = &s + 1i32;
-        }
-        // assert p == t * 2^s + 1 and t % 2 == 1
-        let ni = Self::mod_inv(a, p);
-        let mut c = Self::mod_pow(&b, &t, p);
-        let mut r = Self::mod_pow(&ni, &((&t + &one) / ibig!(2)), p);
-
-        // for k in 1..s
-        let mut k = ibig!(1);
-        while k < s {
-            let b = Self::mod_pow(&r, &ibig!(2), p) * &ni % p;
-            let e = &Self::mod_pow(&ibig!(2), &(&s - &k - &one), p);
-            let d = Self::mod_pow(&b, e, p);
-            if d == p - &one {
-                r = &r * &c % p;
-            }
-            c = &c * &c % p;
-            k = &k + &one;
-        }
-        r // or (p - r)
-    }
-
     fn to_hex(&self) -> String {
         format!("{:x}", self)
     }
@@ -213,11 +316,19 @@ impl Number for IBig {
         ui.bit(bit)
     }
 
+    fn low_bits(&self, k: usize) -> Self {
+        self & ((IBig::one() << k) - 1)
+    }
+
     fn from_bytes_be(bytes: &[u8]) -> Self {
         IBig::from(UBig::from_be_bytes(bytes))
     }
 
+    /// Big-endian magnitude bytes ([0] for 0, as BigInt)
     fn to_bytes_be(&self) -> Vec<u8> {
+        if self.is_zero() {
+            return vec![0];
+        }
         let ui = UBig::try_from(self).unwrap();
         ui.to_be_bytes()
     }
@@ -303,43 +414,6 @@ impl Number for BigInt {
         }
     }
 
-    /// Tonelli's Algorithm
-    #[allow(clippy::many_single_char_names)]
-    fn mod_sqrt(a: &Self, p: &BigInt) -> BigInt {
-        let mut rng = rand::thread_rng();
-
-        // b: some non-quadratic-residue
-        let mut b = BigInt::zero();
-        while b.is_zero() || b.jacobi(p) != -1 {
-            b = rng.gen_bigint_range(&BigInt::one(), &(p - 1));
-        }
-        // p = t * 2^s + 1, t is odd
-        let mut t = p - BigInt::one();
-        let mut s = BigInt::zero();
-        while &t & BigInt::one() == BigInt::zero() {
-            t = &t >> 1u32;
-            s = &s + 1u32;
-        }
-        // assert p == t * 2^s + 1 and t % 2 == 1
-        let ni = Self::mod_inv(a, p);
-        let mut c = b.modpow(&t, p);
-        let mut r = a.modpow(&((&t + 1u32) / 2u32), p);
-
-        // for k in 1..s
-        let mut k = BigInt::one();
-        while k < s {
-            let b = r.modpow(&BigInt::from(2u32), p) * &ni % p;
-            let e = &BigInt::from(2i32).modpow(&(&s - &k - 1u32), p);
-            let d = b.modpow(e, p);
-            if d == p - 1u32 {
-                r = &r * &c % p;
-            }
-            c = &c * &c % p;
-            k = &k + 1u32;
-        }
-        r // or (p - r)
-    }
-
     fn to_hex(&self) -> String {
         format!("{:x}", self)
     }
@@ -369,6 +443,10 @@ impl Number for BigInt {
         self.bit(bit as u64)
     }
 
+    fn low_bits(&self, k: usize) -> Self {
+        self & ((BigInt::one() << k) - 1u32)
+    }
+
     fn from_bytes_be(bytes: &[u8]) -> Self {
         BigInt::from_bytes_be(Sign::Plus, bytes)
     }
@@ -0,0 +1,148 @@
+//!
+//! Modular Reduction Strategies for the Curve Prime
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+use super::montgomery::Montgomery;
+use super::number::{add_mod, half_mod, sub_mod, Number};
+
+///   Special-form prime p = 2^k - c
+///   t = t_hi * 2^k + t_lo = t_hi * c + t_lo (mod p)
+#[derive(Clone, Debug)]
+pub struct SpecialPrime<T: Number> {
+    p: T,
+    k: usize,
+    /// c = 2^k - p
+    c: T,
+}
+
+impl<T: Number> SpecialPrime<T> {
+    /// Special-form prime p
+    /// - panics if p is not of the form 2^k - c (see try_new)
+    pub fn new(p: &T) -> SpecialPrime<T> {
+        Self::try_new(p).expect("prime is not of the form 2^k - c")
+    }
+
+    /// Special-form prime p = 2^k - c with bitlen(c) <= k / 2,  None otherwise
+    /// - pseudo-Mersenne:  secp-k curves, secp160r1/r2, secp521r1 (2^521 - 1), edwards25519 (2^255 - 19)
+    /// - Solinas:  P-192 (c = 2^64 + 1), P-224 (c = 2^96 - 1), P-384 (c = 2^128 + 2^96 - 2^32 + 1)
+    pub fn try_new(p: &T) -> Option<SpecialPrime<T>> {
+        let k = p.bit_len();
+        let c = T::from(2u32).pow(k as u32) - p;
+        if 2 * c.bit_len() > k {
+            return None;
+        }
+        Some(SpecialPrime { p: p.clone(), k, c })
+    }
+
+    /// Get modulus p
+    pub fn get_prime(&self) -> &T {
+        &self.p
+    }
+
+    /// t mod p  (0 <= t)
+    #[inline]
+    pub fn reduce(&self, t: &T) -> T {
+        t.mod_special(self.k, &self.c, &self.p)
+    }
+}
+
+///   Field arithmetic mod p used by the curve operations
+///   Elements are kept in the internal representation (to_field / from_field)
+#[derive(Clone, Debug)]
+pub enum Reduction<T: Number> {
+    /// Montgomery form (any odd prime)
+    Montgomery(Montgomery<T>),
+    /// Folding reduction (p = 2^k - c)
+    Special(SpecialPrime<T>),
+}
+
+impl<T: Number> Reduction<T> {
+    /// Reduction for a prime p (Montgomery form)
+    pub fn new(p: &T) -> Reduction<T> {
+        Reduction::Montgomery(Montgomery::new(p))
+    }
+
+    /// Reduction for a prime p
+    /// - special-form reduction if p = 2^k - c with a small c (see SpecialPrime::try_new),
+    ///   Montgomery form otherwise
+    pub fn for_prime(p: &T) -> Reduction<T> {
+        match SpecialPrime::try_new(p) {
+            Some(s) => Reduction::Special(s),
+            None => Reduction::new(p),
+        }
+    }
+
+    /// Get modulus p
+    pub fn get_prime(&self) -> &T {
+        match self {
+            Reduction::Montgomery(m) => m.get_prime(),
+            Reduction::Special(s) => s.get_prime(),
+        }
+    }
+
+    /// 1 in the internal representation
+    pub fn one(&self) -> T {
+        match self {
+            Reduction::Montgomery(m) => m.one().clone(),
+            Reduction::Special(_) => T::one(),
+        }
+    }
+
+    /// x = 1 (x: internal representation)
+    pub fn is_one(&self, x: &T) -> bool {
+        match self {
+            Reduction::Montgomery(m) => x == m.one(),
+            Reduction::Special(_) => x.is_one(),
+        }
+    }
+
+    /// x -> internal representation
+    pub fn to_field(&self, x: &T) -> T {
+        match self {
+            Reduction::Montgomery(m) => m.to_mont(x),
+            Reduction::Special(s) => Number::mod_cal(x, &s.p),
+        }
+    }
+
+    /// Internal representation -> x mod p
+    pub fn from_field(&self, x: &T) -> T {
+        match self {
+            Reduction::Montgomery(m) => m.from_mont(x),
+            Reduction::Special(_) => x.clone(),
+        }
+    }
+
+    /// a * b mod p
+    #[inline]
+    pub fn mul(&self, a: &T, b: &T) -> T {
+        match self {
+            Reduction::Montgomery(m) => m.mul(a, b),
+            Reduction::Special(s) => s.reduce(&a.mul_ref(b)),
+        }
+    }
+
+    /// a^2 mod p
+    #[inline]
+    pub fn sqr(&self, a: &T) -> T {
+        self.mul(a, a)
+    }
+
+    /// a + b mod p
+    #[inline]
+    pub fn add(&self, a: &T, b: &T) -> T {
+        add_mod(a, b, self.get_prime())
+    }
+
+    /// a - b mod p
+    #[inline]
+    pub fn sub(&self, a: &T, b: &T) -> T {
+        sub_mod(a, b, self.get_prime())
+    }
+
+    /// a / 2 mod p
+    #[inline]
+    pub fn half(&self, a: &T) -> T {
+        half_mod(a, self.get_prime())
+    }
+}
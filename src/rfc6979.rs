@@ -0,0 +1,87 @@
+//!
+//! Deterministic nonce generation (RFC 6979)
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+use hmac::digest::core_api::BlockSizeUser;
+use hmac::digest::Digest;
+use hmac::{Mac, SimpleHmac};
+
+use super::number::Number;
+
+/// HMAC_DRBG based nonce generator (RFC 6979, 3.2)
+pub struct Rfc6979<T: Number, D: Digest + BlockSizeUser> {
+    q: T,
+    k: Vec<u8>,
+    v: Vec<u8>,
+    _d: std::marker::PhantomData<D>,
+}
+
+impl<T: Number, D: Digest + BlockSizeUser> Rfc6979<T, D> {
+    /// Set up the generator for private key x, group order q and hash value h1
+    pub fn new(x: &T, q: &T, h1: &[u8]) -> Rfc6979<T, D> {
+        let hlen = <D as Digest>::output_size();
+        let rlen = q.bit_len().div_ceil(8);
+
+        // int2octets(x) || bits2octets(h1),  rlen-octet big-endian
+        let mut seed = x.to_bytes_be_pad(rlen);
+        let z1: T = bits2int(h1, q.bit_len());
+        let z2 = if &z1 >= q { z1.sub_ref(q) } else { z1 };
+        seed.extend_from_slice(&z2.to_bytes_be_pad(rlen));
+
+        let mut g = Rfc6979 {
+            q: q.clone(),
+            k: vec![0x00; hlen],
+            v: vec![0x01; hlen],
+            _d: std::marker::PhantomData,
+        };
+        // K = HMAC_K(V || 0x00 || seed),  V = HMAC_K(V)
+        g.k = g.hmac(&[&g.v, &[0x00], &seed]);
+        g.v = g.hmac(&[&g.v]);
+        // K = HMAC_K(V || 0x01 || seed),  V = HMAC_K(V)
+        g.k = g.hmac(&[&g.v, &[0x01], &seed]);
+        g.v = g.hmac(&[&g.v]);
+        g
+    }
+
+    /// Next candidate k in [1, q - 1]
+    pub fn next_k(&mut self) -> T {
+        let qlen = self.q.bit_len();
+        loop {
+            let mut t = Vec::new();
+            while t.len() * 8 < qlen {
+                self.v = self.hmac(&[&self.v]);
+                t.extend_from_slice(&self.v);
+            }
+            let k: T = bits2int(&t, qlen);
+
+            // K = HMAC_K(V || 0x00),  V = HMAC_K(V)  (also for a retry by caller)
+            self.k = self.hmac(&[&self.v, &[0x00]]);
+            self.v = self.hmac(&[&self.v]);
+            if !k.is_zero() && k < self.q {
+                return k;
+            }
+        }
+    }
+
+    fn hmac(&self, data: &[&[u8]]) -> Vec<u8> {
+        let mut mac = <SimpleHmac<D> as Mac>::new_from_slice(&self.k).unwrap();
+        for d in data {
+            mac.update(d);
+        }
+        mac.finalize().into_bytes().to_vec()
+    }
+}
+
+/// bits2int: leftmost qlen bits of the octet string
+/// - only the leftmost ceil(qlen / 8) octets are read (a long input fits any T)
+pub fn bits2int<T: Number>(b: &[u8], qlen: usize) -> T {
+    let b = &b[..b.len().min(qlen.div_ceil(8))];
+    let x = T::from_bytes_be(b);
+    let blen = b.len() * 8;
+    if blen > qlen {
+        x >> (blen - qlen)
+    } else {
+        x
+    }
+}
@@ -0,0 +1,234 @@
+//!
+//! Schnorr Signatures (BIP-340)
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+use std::marker::PhantomData;
+
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+use super::batch::{verify_equations, Equation};
+use super::number::Number;
+use super::{EcError, EcOp, Point};
+
+/// Schnorr signature (r = x(R), s)
+#[derive(Clone, Debug, PartialEq)]
+pub struct SchnorrSignature<T> {
+    r: T,
+    s: T,
+}
+
+impl<T: Number> SchnorrSignature<T> {
+    pub fn new(r: T, s: T) -> SchnorrSignature<T> {
+        SchnorrSignature { r, s }
+    }
+
+    /// Get r
+    pub fn r(&self) -> &T {
+        &self.r
+    }
+
+    /// Get s
+    pub fn s(&self) -> &T {
+        &self.s
+    }
+
+    /// bytes(r) || bytes(s),  len octets each
+    pub fn to_bytes(&self, len: usize) -> Vec<u8> {
+        let mut sig = self.r.to_bytes_be_pad(len);
+        sig.extend_from_slice(&self.s.to_bytes_be_pad(len));
+        sig
+    }
+
+    /// Split bytes(r) || bytes(s) (no range check, see verify)
+    /// - Err if a half is longer than T holds (see Number::max_bits)
+    pub fn from_bytes(sig: &[u8]) -> Result<SchnorrSignature<T>, EcError> {
+        if sig.is_empty()
+            || !sig.len().is_multiple_of(2)
+            || T::max_bits().is_some_and(|m| 4 * sig.len() > m)
+        {
+            return Err(EcError::InvalidLength(sig.len()));
+        }
+        let (r, s) = sig.split_at(sig.len() / 2);
+        Ok(SchnorrSignature {
+            r: T::from_bytes_be(r),
+            s: T::from_bytes_be(s),
+        })
+    }
+}
+
+/// hash_tag(x) = SHA256(SHA256(tag) || SHA256(tag) || x)
+pub fn tagged_hash(tag: &str, data: &[&[u8]]) -> [u8; 32] {
+    let th = Sha256::digest(tag.as_bytes());
+    let mut h = Sha256::new();
+    h.update(th);
+    h.update(th);
+    for d in data {
+        h.update(d);
+    }
+    h.finalize().into()
+}
+
+/// Schnorr signatures with x-only public keys (BIP-340)
+/// - defined for secp256k1;  32-octet encodings (field length) in that case
+pub struct Schnorr<'a, T: Number, E: EcOp<T>> {
+    ec: &'a E,
+    _t: PhantomData<T>,
+}
+
+impl<'a, T: Number, E: EcOp<T>> Schnorr<'a, T, E> {
+    pub fn new(ec: &'a E) -> Schnorr<'a, T, E> {
+        Schnorr {
+            ec,
+            _t: PhantomData,
+        }
+    }
+
+    /// Encoding length of x-only keys, r and s
+    pub fn get_len(&self) -> usize {
+        self.ec.get_field_len()
+    }
+
+    /// Generate key pair (d, x(d * G)),  1 <= d < n
+    pub fn gen_keypair(&self) -> (T, T) {
+        let d = Number::gen_rand(&T::one(), &self.ec.get_order());
+        let (_, px) = self.key_point(&d);
+        (d, px)
+    }
+
+    /// x-only public key x(d * G)
+    pub fn public_key(&self, d: &T) -> Result<T, EcError> {
+        if d.is_zero() || d >= &self.ec.get_order() {
+            return Err(EcError::InvalidScalar);
+        }
+        Ok(self.key_point(d).1)
+    }
+
+    /// lift_x: the point with x coordinate x and even y
+    pub fn lift_x(&self, x: &T) -> Result<Point<T>, EcError> {
+        if x >= &self.ec.get_prime() {
+            return Err(EcError::CoordinateOutOfRange);
+        }
+        self.ec
+            .point_from_x(x, 0)
+            .ok_or(EcError::NotQuadraticResidue)
+    }
+
+    /// Sign a message with private key d (random auxiliary data)
+    pub fn sign(&self, d: &T, msg: &[u8]) -> Result<SchnorrSignature<T>, EcError> {
+        let mut aux = vec![0u8; self.get_len()];
+        rand::thread_rng().fill_bytes(&mut aux);
+        self.sign_with_aux(d, msg, &aux)
+    }
+
+    /// Sign a message with private key d and auxiliary random data aux
+    /// - Err(InvalidScalar) if d is not in [1, n - 1] (or the derived nonce is 0)
+    pub fn sign_with_aux(
+        &self,
+        d: &T,
+        msg: &[u8],
+        aux: &[u8],
+    ) -> Result<SchnorrSignature<T>, EcError> {
+        let n = self.ec.get_order();
+        let len = self.get_len();
+        if d.is_zero() || d >= &n {
+            return Err(EcError::InvalidScalar);
+        }
+        // d = d' or n - d' for the even-y point P
+        let (d, px) = self.key_point(d);
+        let px = px.to_bytes_be_pad(len);
+
+        // k' = hash_nonce(bytes(d) xor hash_aux(a) || bytes(P) || m) mod n
+        let t: Vec<u8> = d
+            .to_bytes_be_pad(len)
+            .iter()
+            .zip(tagged_hash("BIP0340/aux", &[aux]).iter())
+            .map(|(a, b)| a ^ b)
+            .collect();
+        let rand = tagged_hash("BIP0340/nonce", &[&t, &px, msg]);
+        let k = T::from_bytes_be(&rand) % &n;
+        if k.is_zero() {
+            return Err(EcError::InvalidScalar);
+        }
+        // R = k' * G,  k = k' or n - k' for the even-y point R
+        let (k, r) = self.key_point(&k);
+        let e = self.challenge(&r, &px, msg);
+
+        // s = k + e * d mod n
+        let s = (k + e * &d) % &n;
+        Ok(SchnorrSignature { r, s })
+    }
+
+    /// Verify a signature with x-only public key px
+    pub fn verify(&self, px: &T, msg: &[u8], sig: &SchnorrSignature<T>) -> bool {
+        let n = self.ec.get_order();
+        let len = self.get_len();
+
+        let p_p = match self.lift_x(px) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        if sig.r >= self.ec.get_prime() || sig.s >= n {
+            return false;
+        }
+        let e = self.challenge(&sig.r, &px.to_bytes_be_pad(len), msg);
+
+        // R = s * G - e * P
+        let p_r = self
+            .ec
+            .mul2(&self.ec.get_gp(), &sig.s, &p_p, &(n.sub_ref(&e) % &n));
+        if self.ec.is_zero(&p_r) {
+            return false;
+        }
+        let p_r = self.ec.to_affine(&p_r);
+        !p_r.y.test_bit(0) && p_r.x == sig.r
+    }
+
+    /// Verify a batch of signatures (px, msg, sig) with one multi-scalar multiplication
+    /// - sum a_i * (R_i + e_i * P_i - s_i * G) = O,  R_i = lift_x(r_i),
+    ///   a_i random (see batch::verify_equations)
+    /// - Err(indices of the invalid signatures)
+    pub fn verify_batch(
+        &self,
+        items: &[(&T, &[u8], &SchnorrSignature<T>)],
+    ) -> Result<(), Vec<usize>> {
+        let eqs: Vec<Option<Equation<T>>> = items
+            .iter()
+            .map(|&(px, msg, sig)| self.equation(px, msg, sig))
+            .collect();
+        verify_equations(self.ec, &eqs)
+    }
+
+    /// R + e * P - s * G = O
+    fn equation(&self, px: &T, msg: &[u8], sig: &SchnorrSignature<T>) -> Option<Equation<T>> {
+        let n = self.ec.get_order();
+        if sig.s >= n {
+            return None;
+        }
+        let p_p = self.lift_x(px).ok()?;
+        let p_r = self.lift_x(&sig.r).ok()?;
+        let e = self.challenge(&sig.r, &px.to_bytes_be_pad(self.get_len()), msg);
+        Some(vec![
+            (p_r, T::one()),
+            (p_p, e),
+            (self.ec.get_gp(), n.sub_ref(&sig.s) % &n),
+        ])
+    }
+
+    /// (d or n - d, x(d * G)) so that d * G has an even y
+    /// - d is secret:  mul_base (point operations independent of d),  n - d is taken with
+    ///   select (branch-free;  constant time only for FixInt, see Number::select)
+    fn key_point(&self, d: &T) -> (T, T) {
+        let p_p = self.ec.to_affine(&self.ec.mul_base(d));
+        let d = T::select(d, &self.ec.get_order().sub_ref(d), p_p.y.test_bit(0));
+        (d, p_p.x)
+    }
+
+    /// e = hash_challenge(bytes(r) || bytes(P) || m) mod n
+    fn challenge(&self, r: &T, px: &[u8], msg: &[u8]) -> T {
+        let r = r.to_bytes_be_pad(self.get_len());
+        let h = tagged_hash("BIP0340/challenge", &[&r, px, msg]);
+        T::from_bytes_be(&h) % &self.ec.get_order()
+    }
+}
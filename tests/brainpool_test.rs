@@ -0,0 +1,156 @@
+//!
+//! Brainpool Curve Test Program
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+extern crate ecc;
+
+use num_bigint::BigInt;
+use sha2::{Digest, Sha256};
+
+use ecc::brainpool::{r1_to_t1, t1_to_r1};
+use ecc::ec_param::EcParam;
+use ecc::ecc_a::EcpA;
+use ecc::ecc_j::EcpJ;
+use ecc::ecdh::Ecdh;
+use ecc::ecdsa::{Ecdsa, Signature};
+use ecc::number::Number;
+use ecc::EcOp;
+
+const SIZES: [u32; 7] = [160, 192, 224, 256, 320, 384, 512];
+
+fn hex(s: &str) -> BigInt {
+    BigInt::from_bytes_radix(s.as_bytes(), 16)
+}
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// RFC 5639 domain parameters
+#[test]
+fn brainpool_param_test() {
+    println!("< Brainpool Parameter Test >");
+    for (i, size) in SIZES.iter().enumerate() {
+        for (j, v) in ["r1", "t1"].iter().enumerate() {
+            let ecname = format!("brainpoolP{}{}", size, v);
+            println!("> {}", ecname);
+            let param = EcParam::new(&ecname);
+            assert_eq!(param.get_ecid(), ecname);
+            let oid = format!("1.3.36.3.3.2.8.1.1.{}", 2 * i + j + 1);
+            assert_eq!(param.get_oid(), oid);
+
+            let ecp: EcpJ<BigInt> = EcpJ::new(&ecname);
+            assert_eq!(ecp.get_prime().bit_len(), *size as usize);
+            let p_g = ecp.get_gp();
+            assert!(ecp.check_point(&p_g).is_ok());
+            assert!(ecp.is_zero(&ecp.mul(&p_g, &ecp.get_order())));
+        }
+    }
+}
+
+/// Jacobian doubling with a != -3 (r1) and a = -3 (t1) against affine
+#[test]
+fn brainpool_double_test() {
+    println!("< Brainpool Double Test >");
+    for size in SIZES.iter() {
+        for v in ["r1", "t1"] {
+            let ecname = format!("brainpoolP{}{}", size, v);
+            println!("> {}", ecname);
+            let eca: EcpA<BigInt> = EcpA::new(&ecname);
+            let ecj: EcpJ<BigInt> = EcpJ::new(&ecname);
+            let is_m3 = ecj.get_a() == ecj.get_prime() - 3;
+            assert_eq!(is_m3, v == "t1");
+
+            let k = Number::gen_rand(&BigInt::from(1), &ecj.get_order());
+            let p_a = eca.mul(&eca.get_gp(), &k);
+            let p_j = ecj.mul(&ecj.get_gp(), &k);
+            let q_a = eca.double(&p_a);
+            let q_j = ecj.to_affine(&ecj.double(&p_j));
+            assert!(ecj.check_point(&q_j).is_ok());
+            assert_eq!(q_a.x, q_j.x);
+            assert_eq!(q_a.y, q_j.y);
+        }
+    }
+}
+
+/// r1 <-> t1 isomorphism
+#[test]
+fn brainpool_twist_test() {
+    println!("< Brainpool r1 <-> t1 Test >");
+    for size in SIZES.iter() {
+        let r1 = format!("brainpoolP{}r1", size);
+        let t1 = format!("brainpoolP{}t1", size);
+        println!("> {} <-> {}", r1, t1);
+        let ecr: EcpJ<BigInt> = EcpJ::new(&r1);
+        let ect: EcpJ<BigInt> = EcpJ::new(&t1);
+
+        // G(t1) = phi(G(r1))
+        let p_g = r1_to_t1(&r1, &ecr.get_gp()).unwrap();
+        assert!(ect.equals(&p_g, &ect.get_gp()));
+        assert!(ecr.equals(&t1_to_r1(&t1, &ect.get_gp()).unwrap(), &ecr.get_gp()));
+
+        // phi(k * P) = k * phi(P) (Jacobian coordinates)
+        let k = Number::gen_rand(&BigInt::from(1), &ecr.get_order());
+        let p_r = ecr.mul(&ecr.get_gp(), &k);
+        let p_t = r1_to_t1(&r1, &p_r).unwrap();
+        assert!(ect.check_point(&ect.to_affine(&p_t)).is_ok());
+        assert!(ect.equals(&p_t, &ect.mul(&ect.get_gp(), &k)));
+        assert!(ecr.equals(&t1_to_r1(&t1, &p_t).unwrap(), &p_r));
+    }
+    assert!(
+        r1_to_t1::<BigInt>("brainpoolP256t1", &EcpJ::new("brainpoolP256t1").get_gp()).is_none()
+    );
+    assert!(t1_to_r1::<BigInt>("secp256r1", &EcpJ::new("secp256r1").get_gp()).is_none());
+}
+
+/// ECDH (RFC 7027, A.1 keys) and ECDSA with results from OpenSSL
+#[test]
+fn brainpool_vector_test() {
+    println!("< Brainpool Known Answer Test >");
+    let da = hex("81DB1EE100150FF2EA338D708271BE38300CB54241D79950F77B063039804F1D");
+    let digest = Sha256::digest(b"sample");
+    let vectors = [
+        (
+            "brainpoolP256r1",
+            "048d2d688c6cf93e1160ad04cc4429117dc2c41825e1e9fca0addd34e6f1b39f7b\
+             990c57520812be512641e47034832106bc7d3e8dd0e4c7f1136d7006547cec6a",
+            "89afc39d41d3b327814b80940b042590f96556ec91e6ae7939bce31f3a18bf2b",
+            "10a76fd4b65994323c0a5e3cb54199db1f6cbb7365a80c74bc6e1769d05f4e22",
+            "16139267761192e85ac7a1c88ae9a7654e2a3095a69948a0a3da7982480559b6",
+        ),
+        (
+            "brainpoolP384r1",
+            "045a54db47cb11e2938f4933e7d7b6f0cc2da7dc7e0b2269c80f7e64b57f55bc4d1219205688fadd344bff86ed34c6e11c\
+             02e9ad70d810a58422631ddb98ea0b2841a67641d5d8667f13930ec8acd5045da38cc2392c0fb6d540c0bdaab60b61d6",
+            "2e8152b0883f281c6aa7bce309cdb569653a4507c78a95ddf74cbab920fce22dc4fb3228611ab4420d2f0a58e5ec4542",
+            "3d5c45aacadbdc74eb8b29d45e56124cb7696b2fcadb8d2b90c29d4eaab43af0b2d89e15b7afbf7af43605adfcdd1ff4",
+            "1b0288edde664c2d47843c51ebfe6a9951a38d1846faf3c04983d2522972d6250213e36fb47b62ad3f3a9881e94d695e",
+        ),
+        (
+            "brainpoolP512r1",
+            "041e916cfa125f662868951561f512a4d7a90372a5e987a322cb943e04199230e5bae4ea2863cbb1add5080ebeeb400c50\
+             5bd9909bb454dba06e680f227bb6e6de49714264fde8b63a0d24e972b0442db9ec93aedd1128856dff02c45e9540b58cda\
+             2d107ab9b4353d1b3bb65cc1bf85ad3da2121adf54e710de2116c09bf69c2e",
+            "057bd7e4572f5469fa480dd6135e26ca4492db0da17b1842b68dda9f4ee85c46\
+             52a2e92af7628d6af8b41fbd45f0fe5afd48557d1cd77c46b9072d07e090875f",
+            "5130c13ac1bc06b0b9c67c946548085c54c21d8d47227b598c3a798807e88b86\
+             e3d1a10cc16288d19c74439d588b3f781898c4be7550722624ee5e6d9d033d15",
+            "27d716bb9349b6fd62a539c5cbbcebcf86855a46d081fbcf05ad40250f1047b3\
+             705b10603d79dbb52a6cc931db11554c366185d5a55fa8726e9b67f3970c50b0",
+        ),
+    ];
+    for (ecname, qb, z, r, s) in vectors.iter() {
+        println!("> {}", ecname);
+        let ecp: EcpJ<BigInt> = EcpJ::new(ecname);
+        let ecdh = Ecdh::new(&ecp);
+        assert_eq!(ecdh.derive(&da, &unhex(qb)).unwrap(), unhex(z));
+
+        let ecdsa = Ecdsa::new(&ecp);
+        let q = ecp.decode_point(&unhex(qb));
+        assert!(ecdsa.verify(&q, &digest, &Signature::new(hex(r), hex(s))));
+    }
+}
@@ -0,0 +1,149 @@
+//!
+//! Number Backend Conformance Test Program (BigInt / IBig / FixInt)
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+extern crate ecc;
+
+use ibig::IBig;
+use num_bigint::BigInt;
+use proptest::prelude::*;
+
+use ecc::ec_param::{EcParam, ParamOp};
+use ecc::fixint::Fix521;
+use ecc::number::Number;
+
+/// Results of every Number method as strings
+#[allow(clippy::many_single_char_names)]
+fn run<T: Number>(a: &[u8], b: &[u8], e: &[u8], id: &str) -> Vec<String> {
+    let (x, y, z) = (
+        T::from_bytes_be(a),
+        T::from_bytes_be(b),
+        T::from_bytes_be(e),
+    );
+    let mut r = Vec::new();
+
+    // odd modulus m > 1
+    let mut m = if y.test_bit(0) {
+        y.clone()
+    } else {
+        y.add_ref(&T::one())
+    };
+    if m.is_one() {
+        m = T::from(3u32);
+    }
+    r.push(T::mod_pow(&x, &z, &m).to_string());
+    r.push(T::mod_pow(&x, &T::zero(), &m).to_string());
+    r.push(Number::mod_cal(&(T::zero() - &x), &m).to_string());
+
+    let (g, s, t) = x.exgcd(&m);
+    r.extend([g.to_string(), s.to_string(), t.to_string()]);
+    if g.is_one() {
+        let i = Number::mod_inv(&x, &m);
+        assert!((i.mul_ref(&x) % &m).is_one());
+        r.push(i.to_string());
+    }
+    r.push(x.jacobi(&m).to_string());
+
+    let p: T = EcParam::new(id).get_prime();
+    let xp = x.clone() % &p;
+    r.push(format!(
+        "{:?}",
+        Number::mod_sqrt(&xp, &p).map(|v| v.to_string())
+    ));
+    r.push(format!(
+        "{:?}",
+        Number::mod_sqrt(&(xp.mul_ref(&xp) % &p), &p).is_some()
+    ));
+
+    r.push(x.bit_len().to_string());
+    for i in [
+        0,
+        1,
+        7,
+        63,
+        64,
+        65,
+        255,
+        520,
+        x.bit_len(),
+        x.bit_len().max(1) - 1,
+    ] {
+        r.push(x.test_bit(i).to_string());
+    }
+    for k in [0, 1, 63, 64, 100, 521] {
+        r.push(x.low_bits(k).to_string());
+    }
+    r.push(format!("{:?}", x.to_bytes_be()));
+    r.push(format!("{:?}", x.to_bytes_be_pad(70)));
+    r.push(x.to_hex());
+    r.push(T::from_bytes_radix(x.to_hex().as_bytes(), 16).to_string());
+    r.push(T::from_bytes_radix(x.to_string().as_bytes(), 10).to_string());
+
+    r.push(x.mul_ref(&y).to_string());
+    r.push(x.add_ref(&y).to_string());
+    r.push(x.sub_ref(&y).to_string());
+    r.push((x.clone() >> 7).to_string());
+    r.push((x.clone() / &m).to_string());
+    r.push((x.clone() % &m).to_string());
+    r.push(format!("{:?}", x.partial_cmp(&y)));
+    let ny = T::zero() - &y;
+    for choice in [false, true] {
+        r.push(T::select(&x, &ny, choice).to_string());
+        r.push(T::select(&ny, &x, choice).to_string());
+    }
+    r
+}
+
+fn check(a: &[u8], b: &[u8], e: &[u8], id: &str) {
+    let rb = run::<BigInt>(a, b, e, id);
+    let ri = run::<IBig>(a, b, e, id);
+    let rf = run::<Fix521>(a, b, e, id);
+    assert_eq!(rb, ri, "IBig");
+    assert_eq!(rb, rf, "FixInt");
+}
+
+#[test]
+fn conformance_edge_test() {
+    println!("< Number Conformance Edge Test >");
+    let v: [&[u8]; 6] = [&[], &[0], &[1], &[2], &[0xff; 8], &[0xff; 66]];
+    for a in v.iter() {
+        for b in v.iter() {
+            for e in v.iter() {
+                check(a, b, e, "secp521r1");
+            }
+        }
+    }
+}
+
+#[test]
+fn ibig_mod_pow_test() {
+    println!("< IBig mod_pow Test >");
+    let (a, m) = (IBig::from(3u32), IBig::from(7u32));
+    for e in 0u32..20 {
+        let r = Number::mod_pow(&a, &IBig::from(e), &m);
+        assert_eq!(r, IBig::from(3u32.pow(e % 6) % 7));
+    }
+    assert_eq!(
+        Number::mod_pow(&IBig::from(-2), &IBig::from(3u32), &m),
+        IBig::from(6u32)
+    );
+    assert_eq!(
+        Number::mod_pow(&IBig::from(5u32), &IBig::from(0u32), &IBig::from(1u32)),
+        IBig::from(0u32)
+    );
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn conformance_prop_test(
+        a in proptest::collection::vec(any::<u8>(), 0..67),
+        b in proptest::collection::vec(any::<u8>(), 0..67),
+        e in proptest::collection::vec(any::<u8>(), 0..67),
+        i in 0..EcParam::curve_ids().len(),
+    ) {
+        check(&a, &b, &e, EcParam::curve_ids()[i]);
+    }
+}
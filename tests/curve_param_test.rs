@@ -0,0 +1,155 @@
+//!
+//! Custom Curve Parameter Test Program
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+extern crate ecc;
+
+use ibig::IBig;
+use num_bigint::BigInt;
+use num_traits::One;
+
+use ecc::curve_param::{CurveParam, ParamError};
+use ecc::ec_param::{EcParam, ParamOp};
+use ecc::ecc_a::EcpA;
+use ecc::ecc_j::EcpJ;
+use ecc::number::Number;
+use ecc::EcOp;
+
+fn hex(s: &str) -> BigInt {
+    BigInt::from_bytes_radix(s.as_bytes(), 16)
+}
+
+/// Replace one parameter of secp256k1 and validate
+fn check_k1(f: impl Fn(&mut [BigInt; 7]), err: ParamError) {
+    let c: CurveParam<BigInt> = CurveParam::from(&EcParam::new("secp256k1"));
+    let mut v = [
+        c.get_prime(),
+        c.get_a(),
+        c.get_b(),
+        c.get_gx(),
+        c.get_gy(),
+        c.get_order(),
+        c.get_cofactor(),
+    ];
+    f(&mut v);
+    let [p, a, b, gx, gy, n, h] = v;
+    let c = CurveParam::new("bad", p, a, b, (gx, gy), n, h);
+    assert_eq!(c.validate(), Err(err));
+}
+
+#[test]
+fn builtin_param_test() {
+    println!("< Built-in Curve Validation Test >");
+    for id in EcParam::curve_ids() {
+        println!("> {}", id);
+        let c: CurveParam<BigInt> = CurveParam::from(&EcParam::new(id));
+        assert_eq!(c.get_ecid(), id);
+        assert_eq!(c.validate(), Ok(()));
+    }
+    let c: CurveParam<IBig> = CurveParam::from(&EcParam::new("secp192r1"));
+    assert_eq!(c.validate(), Ok(()));
+}
+
+#[test]
+fn custom_param_test() {
+    println!("< Custom Curve Test >");
+    // secp256k1 loaded at runtime
+    let c: CurveParam<BigInt> = CurveParam::from_hex(
+        "secp256k1",
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+        "0",
+        "7",
+        "0479BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798\
+         483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+        "1",
+    )
+    .unwrap();
+    assert_eq!(c.validate(), Ok(()));
+    assert_eq!(c, CurveParam::from(&EcParam::new("secp256k1")));
+
+    let eca = EcpA::from_param(&c);
+    let ecj = EcpJ::from_param(&c);
+    let ecr: EcpJ<BigInt> = EcpJ::new("secp256k1");
+    let k = BigInt::gen_rand(&BigInt::one(), &c.get_order());
+    let p_a = eca.mul(&eca.get_gp(), &k);
+    let p_j = ecj.to_affine(&ecj.mul(&ecj.get_gp(), &k));
+    let p_r = ecr.to_affine(&ecr.mul(&ecr.get_gp(), &k));
+    assert!(p_a.x == p_r.x && p_a.y == p_r.y);
+    assert!(p_j.x == p_r.x && p_j.y == p_r.y);
+
+    println!("> invalid hex");
+    for (g, h) in [("040", "1"), ("0102", "1"), ("04zz", "1"), ("0401", "")] {
+        let r: Result<CurveParam<BigInt>, _> = CurveParam::from_hex("t", "17", "1", "1", g, "7", h);
+        assert_eq!(r.err(), Some(ParamError::InvalidHex));
+    }
+}
+
+#[test]
+fn invalid_param_test() {
+    println!("< Invalid Curve Parameter Test >");
+    check_k1(|v| v[0] += 2, ParamError::FieldNotPrime);
+    check_k1(|v| v[0] = BigInt::from(3), ParamError::FieldNotPrime);
+    check_k1(
+        |v| v[2] = v[0].clone() + 7,
+        ParamError::CoefficientOutOfRange,
+    );
+    check_k1(
+        |v| v[3] = BigInt::from(-1),
+        ParamError::CoefficientOutOfRange,
+    );
+    check_k1(|v| v[2] = BigInt::from(0), ParamError::SingularCurve);
+    check_k1(|v| v[4] += 1, ParamError::BaseNotOnCurve);
+    check_k1(|v| v[5] += 1, ParamError::OrderNotPrime);
+    // prime n' != n
+    check_k1(|v| v[5] = v[0].clone(), ParamError::WrongBaseOrder);
+    check_k1(|v| v[6] = BigInt::from(2), ParamError::InvalidCofactor);
+    check_k1(|v| v[6] = BigInt::from(0), ParamError::InvalidCofactor);
+
+    // h = floor((sqrt(p) + 1)^2 / n) = 4
+    let c: CurveParam<BigInt> = CurveParam::from(&EcParam::new("secp128r2"));
+    assert_eq!(c.get_cofactor(), BigInt::from(4));
+    for h in [3, 5] {
+        let c = CurveParam::new(
+            "bad",
+            c.get_prime(),
+            c.get_a(),
+            c.get_b(),
+            (c.get_gx(), c.get_gy()),
+            c.get_order(),
+            BigInt::from(h),
+        );
+        assert_eq!(c.validate(), Err(ParamError::InvalidCofactor));
+    }
+
+    println!("> supersingular curve (y^2 = x^3 + x,  p = 3 mod 4)");
+    let c = CurveParam::new(
+        "mov",
+        hex("eb6e42f7cbf2946a3b94e592297bb6cb"),
+        BigInt::one(),
+        BigInt::from(0),
+        (
+            hex("4ac9060b7d349c54786b6398e6cf9842"),
+            hex("4ab0d1a9ca89cd9f6b5a421ddf3425a4"),
+        ),
+        hex("3adb90bdf2fca51a8ee539648a5eedb3"),
+        BigInt::from(4),
+    );
+    assert_eq!(c.validate(), Err(ParamError::MovCondition));
+
+    println!("> anomalous curve (#E = p)");
+    let c = CurveParam::new(
+        "anomalous",
+        hex("50db00f0d33835feb430f832308e48bf"),
+        BigInt::from(0),
+        BigInt::from(6),
+        (
+            hex("464fbfa348435be8f3ea42d92d3a4d56"),
+            hex("42e8565c874793608941da6606dd72e3"),
+        ),
+        hex("50db00f0d33835feb430f832308e48bf"),
+        BigInt::one(),
+    );
+    assert_eq!(c.validate(), Err(ParamError::AnomalousCurve));
+}
@@ -11,16 +11,20 @@ use num_traits::{One, Zero};
 use std::{env, str};
 use std::time::SystemTime;
 
-use ecc::ec_param::{EcParam, ParamOp};
+use ecc::ec_param::{EcParam, ParamOp, UnknownCurve};
 use ecc::ecc_a::EcpA;
+use ecc::ecc_e::EcpE;
 use ecc::ecc_j::EcpJ;
-use ecc::{EcAxis, EcOp, Point};
+use ecc::ed_param::EdParam;
+use ecc::fixint::Fix256;
+use ecc::number::Number;
+use ecc::{get_wnaf_size, wnaf, EcAxis, EcError, EcOp, Point, PointFormat, WeierstrassOp};
 
 #[test]
 fn ecc_test() {
     println!("< EC Test >");
     let args: Vec<String> = env::args().collect();
-    let ecname = if args.len() == 2 {
+    let ecname = if args.len() == 2 && EcParam::try_new(&args[1]).is_ok() {
         &args[1]
     } else {
         "secp160k1"
@@ -184,7 +188,7 @@ fn test_bigint(ecname: &str, param: &EcParam) {
     println!(" G: {}", &_g_p);
 
     do_test_bi(&eca);
-    do_perform_bi(&eca, &n);
+    do_perform(&eca, &n);
 
     println!("\n< 3D (Jacobian) >");
     let gx = param.get_gx();
@@ -232,7 +236,7 @@ fn test_bigint(ecname: &str, param: &EcParam) {
     println!(" R: {}", &p_a);
 
     do_test_bi(&ecj);
-    do_perform_bi(&ecj, &n);
+    do_perform(&ecj, &n);
 }
 
 fn do_test(ecp: &impl EcOp<IBig>) {
@@ -288,7 +292,7 @@ fn get_g_bi(param: &EcParam) -> Vec<u8> {
 }
 
 /// Performance test
-fn do_perform(ecp: &impl EcOp<IBig>, k: &IBig) {
+fn do_perform<T: Number>(ecp: &impl EcOp<T>, k: &T) {
     println!("> EC mul performance");
     println!(" k = {}", k);
     let count = 25;
@@ -362,30 +366,669 @@ fn do_test_bi(ecp: &impl EcOp<BigInt>) {
     assert!(ecp.is_zero(&p_q));
 }
 
-/// Performance test
-fn do_perform_bi(ecp: &impl EcOp<BigInt>, k: &BigInt) {
-    println!("> EC mul performance");
-    println!(" k = {}", k);
-    let count = 25;
+/// Performance comparison of the Number implementations (Jacobian)
+#[test]
+fn perform_test() {
+    println!("< Performance Test >");
+    let ecname = "secp256r1";
+    let param = EcParam::new(ecname);
+    println!(" EC Name: {}", param.get_ecid());
 
-    let mut p_q = ecp.get_gp();
+    println!("\n< ibig >");
+    let n: IBig = param.get_order();
+    do_perform(&EcpJ::<IBig>::new(ecname), &n);
+
+    println!("\n< bigint >");
+    let n: BigInt = param.get_order();
+    do_perform(&EcpJ::<BigInt>::new(ecname), &n);
+
+    println!("\n< fixint (Fix256: 4 x u64 field, 13 x u64 storage) >");
+    let n: Fix256 = param.get_order();
+    do_perform(&EcpJ::<Fix256>::new(ecname), &n);
+}
+
+#[test]
+fn decode_test() {
+    println!("< Point decoding Test >");
+    let ecname = "secp256k1";
+    let param = EcParam::new(ecname);
+
+    let eca: EcpA<BigInt> = EcpA::new(ecname);
+    do_decode_test(&eca, &get_g_bi(&param));
+    let ecj: EcpJ<BigInt> = EcpJ::new(ecname);
+    do_decode_test(&ecj, &get_g_bi(&param));
+}
+
+fn do_decode_test(ecp: &impl WeierstrassOp<BigInt>, g: &[u8]) {
     let p_g = ecp.get_gp();
+    let ln = ecp.get_field_len();
 
-    // Binary method
-    let s_time = SystemTime::now();
-    for _ in 1..count {
-        p_q = ecp.mul_bin(&p_g, k);
+    println!("> valid encodings");
+    assert!(ecp.equals(&ecp.try_decode_point(g).unwrap(), &p_g));
+    let enc = ecp.get_encoded(&p_g);
+    assert!(ecp.equals(&ecp.try_decode_point(&enc).unwrap(), &p_g));
+    assert!(ecp.is_zero(&ecp.try_decode_point(&[0x00]).unwrap()));
+
+    println!("> invalid prefix / length");
+    assert_eq!(
+        ecp.try_decode_point(&[]).err(),
+        Some(EcError::InvalidLength(0))
+    );
+    assert_eq!(
+        ecp.try_decode_point(&[0x01]).err(),
+        Some(EcError::InvalidPrefix(0x01))
+    );
+    let mut bad = g.to_vec();
+    bad[0] = 0x05;
+    assert_eq!(
+        ecp.try_decode_point(&bad).err(),
+        Some(EcError::InvalidPrefix(0x05))
+    );
+    assert_eq!(
+        ecp.try_decode_point(&g[..g.len() - 1]).err(),
+        Some(EcError::InvalidLength(2 * ln))
+    );
+    assert_eq!(
+        ecp.try_decode_point(&enc[..ln]).err(),
+        Some(EcError::InvalidLength(ln))
+    );
+    assert_eq!(
+        ecp.try_decode_point(&[0x00, 0x00]).err(),
+        Some(EcError::InvalidLength(2))
+    );
+
+    println!("> coordinate out of range");
+    let mut bad = vec![0x02];
+    bad.extend_from_slice(&ecp.get_prime().to_bytes_be().1);
+    assert_eq!(
+        ecp.try_decode_point(&bad).err(),
+        Some(EcError::CoordinateOutOfRange)
+    );
+
+    println!("> not a quadratic residue");
+    let p = ecp.get_prime();
+    let mut x = BigInt::one();
+    loop {
+        let y2 = (&x * &x * &x + ecp.get_a() * &x + ecp.get_b()) % &p;
+        if y2.jacobi(&p) == -1 {
+            break;
+        }
+        x += 1;
     }
-    let e_time = s_time.elapsed().expect("Clock may have gone backwards");
-    assert!(ecp.on_curve(&p_q));
-    println!(" time: {:?}  (bin)", e_time / count);
+    let mut bad = vec![0u8; ln + 1];
+    bad[0] = 0x02;
+    let xb = x.to_bytes_be().1;
+    bad[ln + 1 - xb.len()..].copy_from_slice(&xb);
+    assert_eq!(
+        ecp.try_decode_point(&bad).err(),
+        Some(EcError::NotQuadraticResidue)
+    );
 
-    // Windows method
-    let s_time = SystemTime::now();
-    for _ in 1..count {
-        p_q = ecp.mul(&p_g, k);
+    println!("> not on curve");
+    let mut bad = g.to_vec();
+    bad[2 * ln] ^= 0x01;
+    assert_eq!(ecp.try_decode_point(&bad).err(), Some(EcError::NotOnCurve));
+}
+
+#[test]
+fn encode_test() {
+    println!("< Point encoding Test >");
+    for ecname in ["secp256r1", "secp521r1", "brainpoolP384t1"] {
+        println!("> {}", ecname);
+        let param = EcParam::new(ecname);
+        let eca: EcpA<BigInt> = EcpA::new(ecname);
+        do_encode_test(&eca, &get_g_bi(&param));
+        let ecj: EcpJ<BigInt> = EcpJ::new(ecname);
+        do_encode_test(&ecj, &get_g_bi(&param));
     }
-    let e_time = s_time.elapsed().expect("Clock may have gone backwards");
-    assert!(ecp.on_curve(&p_q));
-    println!(" time: {:?}  (win)", e_time / count);
+
+    // compressed G of P-256 (OpenSSL)
+    let ecp: EcpJ<BigInt> = EcpJ::new("P-256");
+    let enc = ecp.encode_point(&ecp.get_gp(), PointFormat::Compressed);
+    let g = BigInt::parse_bytes(
+        b"036B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296",
+        16,
+    );
+    assert_eq!(enc, g.unwrap().to_bytes_be().1);
+    assert_eq!(ecp.get_encoded(&ecp.get_gp()), enc);
+}
+
+fn do_encode_test(ecp: &impl EcOp<BigInt>, g: &[u8]) {
+    let ln = ecp.get_field_len();
+    let formats = [
+        (PointFormat::Compressed, ln + 1),
+        (PointFormat::Uncompressed, 2 * ln + 1),
+        (PointFormat::Hybrid, 2 * ln + 1),
+    ];
+
+    assert_eq!(
+        ecp.encode_point(&ecp.get_gp(), PointFormat::Uncompressed),
+        g
+    );
+    for (format, len) in formats.iter() {
+        assert_eq!(ecp.encode_point(&ecp.get_zero(), *format), [0x00]);
+        for _ in 0..4 {
+            let k = BigInt::gen_rand(&BigInt::one(), &ecp.get_order());
+            let p_p = ecp.mul(&ecp.get_gp(), &k);
+            let enc = ecp.encode_point(&p_p, *format);
+            assert_eq!(enc.len(), *len);
+            let yt = u8::from(ecp.to_affine(&p_p).y.test_bit(0));
+            match format {
+                PointFormat::Compressed => assert_eq!(enc[0], 0x02 | yt),
+                PointFormat::Uncompressed => assert_eq!(enc[0], 0x04),
+                PointFormat::Hybrid => assert_eq!(enc[0], 0x06 | yt),
+            }
+            assert!(ecp.equals(&ecp.try_decode_point(&enc).unwrap(), &p_p));
+        }
+    }
+
+    println!("> hybrid parity");
+    let mut bad = ecp.encode_point(&ecp.get_gp(), PointFormat::Hybrid);
+    bad[0] ^= 0x01;
+    assert_eq!(
+        ecp.try_decode_point(&bad).err(),
+        Some(EcError::HybridParity)
+    );
+}
+
+#[test]
+fn fixed_length_test() {
+    println!("< Fixed length encoding Test >");
+    println!("> to_bytes_be_pad");
+    assert_eq!(BigInt::from(0).to_bytes_be_pad(3), [0, 0, 0]);
+    assert_eq!(IBig::from(0u32).to_bytes_be_pad(3), [0, 0, 0]);
+    assert_eq!(BigInt::from(0x0102).to_bytes_be_pad(4), [0, 0, 1, 2]);
+    assert_eq!(IBig::from(0x0102u32).to_bytes_be_pad(2), [1, 2]);
+    assert!(std::panic::catch_unwind(|| BigInt::from(0x0102).to_bytes_be_pad(1)).is_err());
+
+    for ecname in ["secp160k1", "secp256k1", "brainpoolP320r1"] {
+        println!("> {}: point with small x", ecname);
+        let eca: EcpA<BigInt> = EcpA::new(ecname);
+        do_fixed_length_test(&eca);
+        let ecj: EcpJ<BigInt> = EcpJ::new(ecname);
+        do_fixed_length_test(&ecj);
+    }
+}
+
+fn do_fixed_length_test(ecp: &impl WeierstrassOp<BigInt>) {
+    let p = ecp.get_prime();
+    let ln = ecp.get_field_len();
+
+    // smallest x with a point on the curve (h = 1)
+    let mut x = BigInt::one();
+    while (&x * &x * &x + ecp.get_a() * &x + ecp.get_b()).jacobi(&p) != 1 {
+        x += 1;
+    }
+    let p_p = ecp.point_from_x(&x, 0).unwrap();
+    assert!(ecp.check_point(&p_p).is_ok());
+
+    for format in [
+        PointFormat::Compressed,
+        PointFormat::Uncompressed,
+        PointFormat::Hybrid,
+    ] {
+        let enc = ecp.encode_point(&p_p, format);
+        let len = if format == PointFormat::Compressed {
+            ln + 1
+        } else {
+            2 * ln + 1
+        };
+        assert_eq!(enc.len(), len);
+        assert!(enc[1..ln].iter().all(|&b| b == 0));
+        assert_eq!(<BigInt as Number>::from_bytes_be(&enc[1..ln + 1]), x);
+        assert!(ecp.equals(&ecp.try_decode_point(&enc).unwrap(), &p_p));
+    }
+}
+
+#[test]
+fn mul_ct_test() {
+    println!("< Montgomery ladder Test >");
+    for ecname in ["secp160r1", "secp256k1", "secp128r2"] {
+        println!("> {}", ecname);
+        let eca: EcpA<BigInt> = EcpA::new(ecname);
+        do_mul_ct_test(&eca);
+        let ecj: EcpJ<BigInt> = EcpJ::new(ecname);
+        do_mul_ct_test(&ecj);
+        let eca: EcpA<IBig> = EcpA::new(ecname);
+        do_mul_ct_test(&eca);
+    }
+}
+
+fn do_mul_ct_test<T: Number>(ecp: &impl EcOp<T>) {
+    let p_g = ecp.get_gp();
+    let n = ecp.get_order();
+    let p_p = ecp.mul(&p_g, &T::gen_rand(&T::one(), &n));
+
+    let mut ks = vec![
+        T::zero(),
+        T::one(),
+        T::from(2),
+        n.sub_ref(&T::one()),
+        n.clone(),
+    ];
+    // k > n (cofactor multiplication)
+    ks.push(n.mul_ref(&ecp.get_cofac()) + T::from(5));
+    for _ in 0..4 {
+        ks.push(T::gen_rand(&T::one(), &n));
+    }
+    for k in ks.iter() {
+        assert!(ecp.equals(&ecp.mul_ct(&p_g, k), &ecp.mul(&p_g, k)));
+        assert!(ecp.equals(&ecp.mul_ct(&p_p, k), &ecp.mul_bin(&p_p, k)));
+    }
+    assert!(ecp.is_zero(&ecp.mul_ct(&p_g, &n)));
+}
+
+#[test]
+fn mul_base_test() {
+    println!("< Fixed-base multiplication Test >");
+    for ecname in ["secp160r1", "secp256k1", "secp128r2", "brainpoolP256r1"] {
+        println!("> {}", ecname);
+        let eca: EcpA<BigInt> = EcpA::new(ecname);
+        do_mul_base_test(&eca);
+        let ecj: EcpJ<BigInt> = EcpJ::new(ecname);
+        do_mul_base_test(&ecj);
+        let ecj: EcpJ<IBig> = EcpJ::new(ecname);
+        do_mul_base_test(&ecj);
+        let ecj: EcpJ<Fix256> = EcpJ::new(ecname);
+        do_mul_base_test(&ecj);
+    }
+    let ecj: EcpJ<BigInt> = EcpJ::new("secp521r1");
+    do_mul_base_test(&ecj);
+
+    // a clone keeps the table built by the first mul_base
+    let ecj2 = ecj.clone();
+    let k = BigInt::from(12345);
+    assert!(ecj2.equals(&ecj2.mul_base(&k), &ecj.mul(&ecj.get_gp(), &k)));
+}
+
+fn do_mul_base_test<T: Number>(ecp: &impl EcOp<T>) {
+    let p_g = ecp.get_gp();
+    let n = ecp.get_order();
+
+    let mut ks = vec![
+        T::zero(),
+        T::one(),
+        T::from(2),
+        T::from(31),
+        T::from(32),
+        n.sub_ref(&T::one()),
+        n.sub_ref(&T::from(2)),
+        n.clone(),
+    ];
+    // k > n (cofactor multiplication)
+    ks.push(n.mul_ref(&ecp.get_cofac()) + T::from(5));
+    for _ in 0..8 {
+        ks.push(T::gen_rand(&T::one(), &n));
+    }
+    for k in ks.iter() {
+        let p_q = ecp.mul_base(k);
+        assert!(ecp.on_curve(&p_q));
+        assert!(ecp.equals(&p_q, &ecp.mul(&p_g, k)));
+    }
+    assert!(ecp.is_zero(&ecp.mul_base(&n)));
+}
+
+#[test]
+fn wnaf_test() {
+    println!("< wNAF Test >");
+    let mut ks: Vec<BigInt> = vec![
+        BigInt::one(),
+        BigInt::from(7),
+        BigInt::from(0xffffu32),
+        BigInt::from(2).pow(255) - 1,
+    ];
+    for _ in 0..16 {
+        ks.push(BigInt::gen_rand(&BigInt::one(), &BigInt::from(2).pow(521)));
+    }
+    assert!(wnaf(&BigInt::zero(), 4).is_empty());
+    for k in ks.iter() {
+        for w in 2..=8 {
+            let naf = wnaf(k, w);
+            // k = sum d_i * 2^i,  d_i odd,  |d_i| < 2^(w-1)
+            let mut v = BigInt::zero();
+            for (i, &d) in naf.iter().enumerate() {
+                v += BigInt::from(d) << i;
+                assert!(d == 0 || (d % 2 != 0 && d.abs() < 1 << (w - 1)));
+            }
+            assert_eq!(&v, k);
+            assert!(naf.len() <= k.bit_len() + 1 && *naf.last().unwrap() > 0);
+            // at most one non-zero digit in any w consecutive digits
+            let nz: Vec<usize> = (0..naf.len()).filter(|&i| naf[i] != 0).collect();
+            assert!(nz.windows(2).all(|p| p[1] - p[0] >= w));
+        }
+    }
+    assert_eq!(get_wnaf_size(&BigInt::from(2).pow(255)), 5);
+    assert_eq!(get_wnaf_size(&BigInt::from(2).pow(520)), 6);
+
+    for ecname in ["secp160r1", "secp256k1", "secp128r2"] {
+        println!("> {}", ecname);
+        let eca: EcpA<BigInt> = EcpA::new(ecname);
+        do_mul_wnaf_test(&eca);
+        let ecj: EcpJ<BigInt> = EcpJ::new(ecname);
+        do_mul_wnaf_test(&ecj);
+        let ecj: EcpJ<Fix256> = EcpJ::new(ecname);
+        do_mul_wnaf_test(&ecj);
+    }
+}
+
+fn do_mul_wnaf_test<T: Number>(ecp: &impl EcOp<T>) {
+    let n = ecp.get_order();
+    let p_p = ecp.mul(&ecp.get_gp(), &T::gen_rand(&T::one(), &n));
+
+    // table[i] = (2i + 1) * P
+    let table = ecp.get_wnaf_table(&p_p, 5);
+    assert_eq!(table.len(), 8);
+    for (i, p_t) in table.iter().enumerate() {
+        assert!(ecp.equals(p_t, &ecp.mul_bin(&p_p, &T::from(2 * i as u32 + 1))));
+    }
+
+    let mut ks = vec![
+        T::zero(),
+        T::one(),
+        T::from(2),
+        T::from(15),
+        n.sub_ref(&T::one()),
+        n.clone(),
+    ];
+    // k > n (cofactor multiplication)
+    ks.push(n.mul_ref(&ecp.get_cofac()) + T::from(5));
+    for _ in 0..4 {
+        ks.push(T::gen_rand(&T::one(), &n));
+    }
+    for k in ks.iter() {
+        assert!(ecp.equals(&ecp.mul_wnaf(&p_p, k), &ecp.mul_bin(&p_p, k)));
+    }
+    assert!(ecp.is_zero(&ecp.mul_wnaf(&ecp.get_gp(), &n)));
+}
+
+#[test]
+fn mul2_test() {
+    println!("< Double-scalar multiplication Test >");
+    for ecname in EcParam::curve_ids() {
+        println!("> {}", ecname);
+        let ecj: EcpJ<BigInt> = EcpJ::new(ecname);
+        do_mul2_test(&ecj);
+    }
+    for ecname in ["secp160r1", "secp128r2"] {
+        let eca: EcpA<BigInt> = EcpA::new(ecname);
+        do_mul2_test(&eca);
+        let ecj: EcpJ<IBig> = EcpJ::new(ecname);
+        do_mul2_test(&ecj);
+        let ecj: EcpJ<Fix256> = EcpJ::new(ecname);
+        do_mul2_test(&ecj);
+    }
+}
+
+fn do_mul2_test<T: Number>(ecp: &impl EcOp<T>) {
+    let p_g = ecp.get_gp();
+    let n = ecp.get_order();
+    let p_q = ecp.mul(&p_g, &T::gen_rand(&T::one(), &n));
+    let naive =
+        |p1: &Point<T>, k: &T, p2: &Point<T>, l: &T| ecp.add(&ecp.mul(p1, k), &ecp.mul(p2, l));
+
+    let n1 = n.sub_ref(&T::one());
+    let mut kls = vec![
+        (T::zero(), T::zero()),
+        (T::zero(), T::from(3)),
+        (T::from(3), T::zero()),
+        (T::one(), T::one()),
+        (n1.clone(), n1.clone()),
+        (n.clone(), T::from(2)),
+        (T::one(), T::from(1u32 << 20)),
+    ];
+    for _ in 0..2 {
+        kls.push((T::gen_rand(&T::one(), &n), T::gen_rand(&T::one(), &n)));
+    }
+    for (k, l) in kls.iter() {
+        assert!(ecp.equals(&ecp.mul2(&p_g, k, &p_q, l), &naive(&p_g, k, &p_q, l)));
+    }
+    // P = Q,  P = -Q
+    let k = T::gen_rand(&T::one(), &n);
+    let l = T::gen_rand(&T::one(), &n);
+    assert!(ecp.equals(&ecp.mul2(&p_q, &k, &p_q, &l), &naive(&p_q, &k, &p_q, &l)));
+    assert!(ecp.is_zero(&ecp.mul2(&p_q, &k, &ecp.negate(&p_q), &k)));
+
+    // multi-scalar:  k * G + l * Q + m * (G + Q)
+    let m = T::gen_rand(&T::one(), &n);
+    let p_s = ecp.add(&p_g, &p_q);
+    let p_r = ecp.mul_multi(&[(&p_g, &k), (&p_q, &l), (&p_s, &m)]);
+    let p_t = ecp.add(&naive(&p_g, &k, &p_q, &l), &ecp.mul(&p_s, &m));
+    assert!(ecp.equals(&p_r, &p_t));
+    assert!(ecp.is_zero(&ecp.mul_multi(&[])));
+}
+
+const SEC2_CURVES: [(&str, &str); 15] = [
+    ("secp112r1", "1.3.132.0.6"),
+    ("secp112r2", "1.3.132.0.7"),
+    ("secp128r1", "1.3.132.0.28"),
+    ("secp128r2", "1.3.132.0.29"),
+    ("secp160k1", "1.3.132.0.9"),
+    ("secp160r1", "1.3.132.0.8"),
+    ("secp160r2", "1.3.132.0.30"),
+    ("secp192k1", "1.3.132.0.31"),
+    ("secp192r1", "1.2.840.10045.3.1.1"),
+    ("secp224k1", "1.3.132.0.32"),
+    ("secp224r1", "1.3.132.0.33"),
+    ("secp256k1", "1.3.132.0.10"),
+    ("secp256r1", "1.2.840.10045.3.1.7"),
+    ("secp384r1", "1.3.132.0.34"),
+    ("secp521r1", "1.3.132.0.35"),
+];
+
+#[test]
+fn curve_param_test() {
+    println!("< Curve Parameter Test >");
+    for (ecname, oid) in SEC2_CURVES.iter() {
+        println!("> {}", ecname);
+        let param = EcParam::new(ecname);
+        assert_eq!(param.get_ecid(), *ecname);
+        assert_eq!(param.get_oid(), *oid);
+        assert_eq!(EcParam::from_oid(oid).unwrap().get_ecid(), *ecname);
+
+        let ecp: EcpJ<BigInt> = EcpJ::new(ecname);
+        let p_g = ecp.get_gp();
+        // G on curve and order n
+        assert!(!ecp.is_zero(&p_g));
+        assert!(ecp.check_point_partial(&p_g).is_ok());
+        assert!(ecp.is_zero(&ecp.mul(&p_g, &ecp.get_order())));
+        assert!(!ecp.is_zero(&ecp.mul(&p_g, &(ecp.get_order() - 1))));
+        // 04 || X || Y at field length
+        assert_eq!(param.g.len(), 2 + 4 * ecp.get_field_len());
+    }
+
+    println!("> NIST aliases");
+    for (alias, ecname) in [
+        ("P-192", "secp192r1"),
+        ("P-224", "secp224r1"),
+        ("P-256", "secp256r1"),
+        ("P-384", "secp384r1"),
+        ("P-521", "secp521r1"),
+    ] {
+        assert_eq!(EcParam::new(alias).get_ecid(), ecname);
+        assert!(EcParam::new(ecname).check_parameters(alias));
+    }
+    assert!(EcParam::from_oid("1.2.3.4").is_none());
+}
+
+#[test]
+fn subgroup_test() {
+    println!("< Subgroup Check Test >");
+    for ecname in ["secp112r2", "secp128r2"] {
+        println!("> {}", ecname);
+        let ecp: EcpA<BigInt> = EcpA::new(ecname);
+        let ln = ecp.get_field_len();
+        assert_eq!(ecp.get_cofac(), BigInt::from(4));
+
+        // random point outside the subgroup of order n
+        let mut p_r = ecp.gen_point();
+        while ecp.is_zero(&ecp.mul(&p_r, &ecp.get_order())) {
+            p_r = ecp.gen_point();
+        }
+        let mut enc = vec![0u8; 2 * ln + 1];
+        enc[0] = 0x04;
+        let xb = p_r.x.to_bytes_be().1;
+        let yb = p_r.y.to_bytes_be().1;
+        enc[ln + 1 - xb.len()..ln + 1].copy_from_slice(&xb);
+        enc[2 * ln + 1 - yb.len()..].copy_from_slice(&yb);
+
+        assert_eq!(ecp.check_point(&p_r).err(), Some(EcError::NotInSubgroup));
+        assert_eq!(
+            ecp.try_decode_point(&enc).err(),
+            Some(EcError::NotInSubgroup)
+        );
+        assert!(ecp.equals(&ecp.try_decode_point_partial(&enc).unwrap(), &p_r));
+    }
+}
+
+#[test]
+fn unknown_curve_test() {
+    println!("< Unknown Curve Test >");
+    let ids = EcParam::curve_ids();
+    assert_eq!(ids.len(), 29);
+    for id in ids.iter() {
+        assert_eq!(EcParam::try_new(id).unwrap().get_ecid(), *id);
+    }
+    assert!(ids.contains(&"secp256r1") && ids.contains(&"brainpoolP512t1"));
+    assert!(EcParam::try_new("P-256").is_ok());
+
+    for id in ["secp256r2", "P256", "", "brainpoolP256"] {
+        let err = UnknownCurve(id.to_string());
+        assert_eq!(EcParam::try_new(id).err(), Some(err.clone()));
+        assert_eq!(EcpA::<BigInt>::try_new(id).err(), Some(err.clone()));
+        assert_eq!(EcpJ::<IBig>::try_new(id).err(), Some(err));
+        assert!(!EcParam::new("secp160k1").check_parameters(id));
+    }
+    // fields that do not fit the number type
+    for id in ["secp384r1", "secp521r1", "brainpoolP320r1"] {
+        assert!(EcpJ::<Fix256>::try_new(id).is_err());
+        assert!(EcpA::<Fix256>::try_new(id).is_err());
+    }
+    assert!(EcpJ::<Fix256>::try_new("secp256r1").is_ok());
+    assert!(EcpE::<Fix256>::try_new("edwards448").is_err());
+    let r = std::panic::catch_unwind(|| EcpJ::<Fix256>::from_param(&EcParam::new("secp521r1")));
+    assert!(r.is_err());
+
+    let mut param = EcParam::new("secp160k1");
+    assert!(param.set_parameters("secp256r2").is_err());
+    assert_eq!(param.get_ecid(), "secp160k1");
+    assert!(param.set_parameters("P-384").is_ok());
+    assert_eq!(param.get_ecid(), "secp384r1");
+
+    let r = std::panic::catch_unwind(|| EcpJ::<BigInt>::new("secp256r2"));
+    assert!(r.is_err());
+}
+
+#[test]
+fn edwards_test() {
+    println!("< Twisted Edwards Curve Test >");
+    for ecname in EdParam::curve_ids() {
+        println!("> {}", ecname);
+        let ece: EcpE<BigInt> = EcpE::new(ecname);
+        do_edwards_test(&ece);
+        do_mul_ct_test(&ece);
+        do_mul_base_test(&ece);
+        do_mul_wnaf_test(&ece);
+        do_mul2_test(&ece);
+        let ece: EcpE<IBig> = EcpE::new(ecname);
+        do_mul_base_test(&ece);
+    }
+    let ece: EcpE<Fix256> = EcpE::new("edwards25519");
+    do_edwards_test(&ece);
+    do_mul2_test(&ece);
+
+    assert_eq!(EdParam::try_new("Ed448").unwrap().get_ecid(), "edwards448");
+    assert_eq!(
+        EdParam::from_oid("1.3.101.112").unwrap().get_ecid(),
+        "edwards25519"
+    );
+    let err = UnknownCurve("secp256k1".to_string());
+    assert_eq!(EcpE::<BigInt>::try_new("secp256k1").err(), Some(err));
+
+    // coefficient d
+    let ece: EcpE<BigInt> = EcpE::new("edwards25519");
+    assert!(ece.get_d() == EdParam::new("edwards25519").get_d::<BigInt>());
+}
+
+fn do_edwards_test<T: Number>(ece: &EcpE<T>) {
+    let p_g = ece.get_gp();
+    let p_0 = ece.get_zero();
+    let n = ece.get_order();
+    let p = ece.get_prime();
+
+    // group law:  O neutral,  P + (-P) = O,  2P = P + P,  (P + Q) + G = P + (Q + G)
+    assert!(ece.on_curve(&p_g) && ece.on_curve(&p_0) && ece.is_zero(&p_0));
+    let p_p = ece.mul(&p_g, &T::gen_rand(&T::one(), &n));
+    let p_q = ece.mul(&p_g, &T::gen_rand(&T::one(), &n));
+    assert!(ece.on_curve(&p_p) && !ece.is_zero(&p_p));
+    assert!(ece.equals(&ece.add(&p_p, &p_0), &p_p));
+    assert!(ece.is_zero(&ece.add(&p_p, &ece.negate(&p_p))));
+    assert!(ece.equals(&ece.double(&p_p), &ece.add(&p_p, &p_p)));
+    assert!(ece.is_zero(&ece.double(&p_0)));
+    assert!(ece.equals(
+        &ece.add(&ece.add(&p_p, &p_q), &p_g),
+        &ece.add(&p_p, &ece.add(&p_q, &p_g))
+    ));
+    assert!(ece.is_zero(&ece.mul(&p_g, &n)));
+    assert!(ece.check_point(&p_p).is_ok());
+
+    // affine form (x, y, 1)
+    let p_a = ece.to_affine(&ece.double(&p_p));
+    assert!(p_a.z.is_one() && ece.on_curve(&p_a));
+    assert!(ece.equals(&p_a, &ece.point_from_xy(&p_a.x, &p_a.y)));
+
+    // points from x / y
+    let p_x = ece
+        .point_from_x(&p_a.x, u32::from(p_a.y.test_bit(0)))
+        .unwrap();
+    let p_y = ece
+        .point_from_y(&p_a.y, u32::from(p_a.x.test_bit(0)))
+        .unwrap();
+    assert!(ece.equals(&p_x, &p_a) && ece.equals(&p_y, &p_a));
+
+    // RFC 8032 encoding
+    let enc = ece.encode_ed(&p_p);
+    assert_eq!(enc.len(), ece.get_encoded_len());
+    assert!(ece.equals(&ece.decode_ed(&enc).unwrap(), &p_p));
+    assert_eq!(ece.encode_ed(&p_0)[0], 0x01);
+    assert_eq!(
+        ece.decode_ed(&enc[1..]).err(),
+        Some(EcError::InvalidLength(enc.len() - 1))
+    );
+    let mut enc_p = p.to_bytes_be_pad(enc.len());
+    enc_p.reverse();
+    assert_eq!(
+        ece.decode_ed(&enc_p).err(),
+        Some(EcError::CoordinateOutOfRange)
+    );
+    // x = 0 with the sign bit set
+    let mut enc_0 = ece.encode_ed(&p_0);
+    enc_0[enc.len() - 1] |= 0x80;
+    assert_eq!(ece.decode_ed(&enc_0).err(), Some(EcError::NotOnCurve));
+
+    // EcOp encoding is the RFC 8032 encoding
+    for format in [PointFormat::Compressed, PointFormat::Uncompressed] {
+        assert_eq!(ece.encode_point(&p_p, format), enc);
+    }
+    assert_eq!(ece.get_encoded(&p_p), enc);
+    assert!(ece.equals(&ece.try_decode_point(&enc).unwrap(), &p_p));
+    assert!(ece.is_zero(&ece.decode_point(&ece.get_encoded(&p_0))));
+    assert_eq!(
+        ece.try_decode_point(&ece.encode_point(&p_p, PointFormat::Compressed)[1..])
+            .err(),
+        Some(EcError::InvalidLength(enc.len() - 1))
+    );
+
+    // (0, -1) has order 2
+    let p_t = ece.point_from_y(&p.sub_ref(&T::one()), 0).unwrap();
+    assert!(ece.on_curve(&p_t) && ece.is_zero(&ece.double(&p_t)));
+    assert_eq!(ece.check_point(&p_t), Err(EcError::NotInSubgroup));
+    let enc_t = ece.get_encoded(&p_t);
+    assert_eq!(
+        ece.try_decode_point(&enc_t).err(),
+        Some(EcError::NotInSubgroup)
+    );
+    assert!(ece.equals(&ece.try_decode_point_partial(&enc_t).unwrap(), &p_t));
+    assert!(ece.calc_order(&p_t) == T::from(2));
+    let p_b = ece.point_from_xy(&T::one(), &T::one());
+    assert_eq!(ece.check_point(&p_b), Err(EcError::NotOnCurve));
 }
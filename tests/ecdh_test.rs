@@ -0,0 +1,163 @@
+//!
+//! ECDH Test Program
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+extern crate ecc;
+
+use num_bigint::BigInt;
+use num_traits::One;
+
+use ecc::ecc_a::EcpA;
+use ecc::ecc_j::EcpJ;
+use ecc::ecdh::{Ecdh, Validation};
+use ecc::number::Number;
+use ecc::{EcError, EcOp};
+
+const CURVES: [&str; 8] = [
+    "secp160k1",
+    "secp160r1",
+    "secp192k1",
+    "secp224k1",
+    "secp224r1",
+    "secp256k1",
+    "secp384r1",
+    "secp521r1",
+];
+
+fn hex(s: &str) -> BigInt {
+    BigInt::from_bytes_radix(s.as_bytes(), 16)
+}
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn ecdh_test() {
+    println!("< ECDH Test >");
+    for ecname in CURVES.iter() {
+        println!("> {}", ecname);
+        let eca: EcpA<BigInt> = EcpA::new(ecname);
+        do_agree(&eca);
+        let ecj: EcpJ<BigInt> = EcpJ::new(ecname);
+        do_agree(&ecj);
+    }
+}
+
+fn do_agree(ecp: &impl EcOp<BigInt>) {
+    for ecdh in [Ecdh::new(ecp), Ecdh::new(ecp).with_cofactor(true)] {
+        let (da, qa) = ecdh.gen_keypair();
+        let (db, qb) = ecdh.gen_keypair();
+        let za = ecdh.derive_point(&da, &qb).unwrap();
+        let zb = ecdh.derive_point(&db, &qa).unwrap();
+        assert_eq!(za.len(), ecp.get_field_len());
+        assert_eq!(za, zb);
+    }
+}
+
+/// Shared secrets computed by an independent implementation (OpenSSL)
+#[test]
+fn ecdh_vector_test() {
+    println!("< ECDH Known Answer Test >");
+    let d = hex("C88F01F510D9AC3F70A292DAA2316DE544E9AAB8AFE84049C62A9C57862D1433");
+
+    let ecp: EcpJ<BigInt> = EcpJ::new("secp256k1");
+    let ecdh = Ecdh::new(&ecp);
+    let q = unhex(
+        "04d9aca70f4d80b36397084957ff0fc36be2c5b90e4b9b99a9239c686576c5638a\
+         ffabb4ba15111085263e3f27b8c3758d6e86762c0539384d9cead06f6a6837c9",
+    );
+    let z = unhex("8e50efe77f25e3f22a0d9c9db8005f46602281023b5524b6353f6df4005a0bd0");
+    assert_eq!(ecdh.derive(&d, &q).unwrap(), z);
+
+    let ecp: EcpA<BigInt> = EcpA::new("secp384r1");
+    let ecdh = Ecdh::new(&ecp).with_cofactor(true);
+    let q = unhex(
+        "042f5ee85cb858e1af05919c0d3c4928f2dc39c88fbd2753e9823ded8813d580851982bae04a2285af49515e0189134e15\
+         09afae03255a1ba1c99ce92ffb76e4286e6f9c144ac75ff0a93302557e03b9d22ba1f55a9bdb62cc53f863844abde71e",
+    );
+    let z = unhex(
+        "570277885fcd1943d3413a4032a3db0adc7dbbb92324f90d2750826c5d2d1bc71d63e7d0c22b04d2227536f2f6e196ac",
+    );
+    assert_eq!(ecdh.derive(&d, &q).unwrap(), z);
+}
+
+#[test]
+fn ecdh_invalid_test() {
+    println!("< ECDH Invalid Input Test >");
+    let ecp: EcpJ<BigInt> = EcpJ::new("secp160k1");
+    let ecdh = Ecdh::new(&ecp);
+    let (d, q) = ecdh.gen_keypair();
+    let enc = ecp.get_encoded(&q);
+
+    println!("> invalid peer key");
+    assert_eq!(ecdh.derive(&d, &[0x00]), Err(EcError::PointAtInfinity));
+    assert_eq!(
+        ecdh.derive(&d, &enc[..enc.len() - 1]),
+        Err(EcError::InvalidLength(enc.len() - 1))
+    );
+    let mut bad = enc.clone();
+    bad[0] = 0x05;
+    assert_eq!(ecdh.derive(&d, &bad), Err(EcError::InvalidPrefix(0x05)));
+    let bad_q = ecp.point_from_xy(&q.x, &(&q.y + BigInt::one()));
+    assert_eq!(ecdh.derive_point(&d, &bad_q), Err(EcError::NotOnCurve));
+    let ecdh = Ecdh::new(&ecp).with_validation(Validation::Partial);
+    assert_eq!(ecdh.derive_point(&d, &bad_q), Err(EcError::NotOnCurve));
+
+    println!("> invalid private key");
+    assert_eq!(
+        ecdh.derive_point(&BigInt::from(0), &q),
+        Err(EcError::InvalidScalar)
+    );
+    assert_eq!(
+        ecdh.derive_point(&ecp.get_order(), &q),
+        Err(EcError::InvalidScalar)
+    );
+
+    println!("> fixed length output");
+    // find d with a short x-coordinate of d * G
+    let p_g = ecp.get_gp();
+    let mut p_p = p_g.clone();
+    let mut d = BigInt::one();
+    while ecp.to_affine(&p_p).x.bit_len() > 8 * (ecp.get_field_len() - 1) {
+        p_p = ecp.add(&p_p, &p_g);
+        d += 1;
+    }
+    let z = ecdh.derive_point(&d, &p_g).unwrap();
+    assert_eq!(z.len(), ecp.get_field_len());
+    assert_eq!(z[0], 0);
+    assert_eq!(<BigInt as Number>::from_bytes_be(&z), ecp.to_affine(&p_p).x);
+}
+
+#[test]
+fn ecdh_cofactor_test() {
+    println!("< ECDH Cofactor Test >");
+    let ecp: EcpA<BigInt> = EcpA::new("secp112r2");
+    let (d, _) = Ecdh::new(&ecp).gen_keypair();
+
+    // point of small order (h * T = O)
+    let mut p_t = ecp.get_zero();
+    while ecp.is_zero(&p_t) {
+        p_t = ecp.mul(&ecp.gen_point(), &ecp.get_order());
+    }
+    assert!(ecp.is_zero(&ecp.mul(&p_t, &ecp.get_cofac())));
+
+    let ecdh = Ecdh::new(&ecp);
+    assert_eq!(ecdh.derive_point(&d, &p_t), Err(EcError::NotInSubgroup));
+    let ecdh = Ecdh::new(&ecp)
+        .with_cofactor(true)
+        .with_validation(Validation::Partial);
+    assert_eq!(ecdh.derive_point(&d, &p_t), Err(EcError::PointAtInfinity));
+
+    // cofactor ECDH agrees on subgroup points
+    let (da, qa) = ecdh.gen_keypair();
+    let (db, qb) = ecdh.gen_keypair();
+    assert_eq!(
+        ecdh.derive_point(&da, &qb).unwrap(),
+        ecdh.derive_point(&db, &qa).unwrap()
+    );
+}
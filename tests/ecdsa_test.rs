@@ -0,0 +1,552 @@
+//!
+//! ECDSA Test Program
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+extern crate ecc;
+
+use num_bigint::BigInt;
+use num_traits::One;
+use sha2::digest::core_api::BlockSizeUser;
+use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
+use sha3::Keccak256;
+
+use ecc::ecc_a::EcpA;
+use ecc::ecc_j::EcpJ;
+use ecc::ecdsa::{Ecdsa, Signature};
+use ecc::fixint::Fix256;
+use ecc::number::Number;
+use ecc::{EcError, EcOp, Point};
+
+const CURVES: [&str; 8] = [
+    "secp160k1",
+    "secp160r1",
+    "secp192k1",
+    "secp224k1",
+    "secp224r1",
+    "secp256k1",
+    "secp384r1",
+    "secp521r1",
+];
+
+fn hex(s: &str) -> BigInt {
+    let s: String = s.split_whitespace().collect();
+    BigInt::from_bytes_radix(s.as_bytes(), 16)
+}
+
+#[test]
+fn ecdsa_test() {
+    println!("< ECDSA Test >");
+    let digest = b"\xaf\x2b\xdb\xe1\xaa\x9b\x6e\xc1\xe2\xad\xe1\xd6\x94\xf4\x1f\xc7\
+                   \x1a\x83\x1d\x02\x68\xe9\x89\x15\x62\x11\x3d\x8a\x62\xad\xd1\xbf";
+
+    for ecname in CURVES.iter() {
+        println!("> {}", ecname);
+        let eca: EcpA<BigInt> = EcpA::new(ecname);
+        do_sign_verify(&eca, digest);
+        let ecj: EcpJ<BigInt> = EcpJ::new(ecname);
+        do_sign_verify(&ecj, digest);
+    }
+}
+
+fn do_sign_verify(ecp: &impl EcOp<BigInt>, digest: &[u8]) {
+    let ecdsa = Ecdsa::new(ecp);
+    let (d, q) = ecdsa.gen_keypair();
+    assert!(ecp.check_point(&q).is_ok());
+
+    let sig = ecdsa.sign(&d, digest);
+    assert!(ecdsa.verify(&q, digest, &sig));
+
+    // tampered digest / signature
+    let mut bad = digest.to_vec();
+    bad[0] ^= 0x80;
+    assert!(!ecdsa.verify(&q, &bad, &sig));
+    let bad_sig = Signature::new(sig.r().clone(), sig.s() + BigInt::one());
+    assert!(!ecdsa.verify(&q, digest, &bad_sig));
+    let bad_sig = Signature::new(sig.r().clone(), ecp.get_order());
+    assert!(!ecdsa.verify(&q, digest, &bad_sig));
+
+    // wrong key
+    let (_, q2) = ecdsa.gen_keypair();
+    assert!(!ecdsa.verify(&q2, digest, &sig));
+}
+
+/// A digest longer than FixInt holds:  only its leftmost bitlen(n) bits are read
+#[test]
+fn long_digest_test() {
+    let ecj: EcpJ<Fix256> = EcpJ::new("secp256r1");
+    let ecdsa = Ecdsa::new(&ecj);
+    let digest = [0xa5u8; 200];
+    assert!(ecdsa.bits2int(&digest) == ecdsa.bits2int(&digest[..32]));
+
+    let (d, q) = ecdsa.gen_keypair();
+    let sig = ecdsa.sign(&d, &digest);
+    assert!(ecdsa.verify(&q, &digest, &sig));
+    assert!(ecdsa.verify(&q, &digest[..32], &sig));
+}
+
+/// Signatures produced by an independent implementation (OpenSSL)
+#[test]
+fn ecdsa_vector_test() {
+    println!("< ECDSA Known Answer Test >");
+    let digest = hex("af2bdbe1aa9b6ec1e2ade1d694f41fc71a831d0268e9891562113d8a62add1bf")
+        .to_bytes_be()
+        .1;
+    let d = hex("1234567890abcdef1234567890abcdef1234567890abcdef12345678");
+
+    // secp256k1, SHA-256
+    let ecp: EcpJ<BigInt> = EcpJ::new("secp256k1");
+    let ecdsa = Ecdsa::new(&ecp);
+    let q = ecp.to_affine(&ecp.mul(&ecp.get_gp(), &d));
+    assert_eq!(
+        q.x,
+        hex("1e6a12694805348e1fc5ec20d985d0de552a1295d1cbde261a8cfe539749bfe2")
+    );
+    assert_eq!(
+        q.y,
+        hex("8aca72f4bda90def52757723d36e57de5fdff3ebd204aa79dead42e5d24dd543")
+    );
+    let sig = Signature::new(
+        hex("dff61c13fab88cb9f9f1888fc87d639d5733b161c1c264c6d0064cfe9d18eb34"),
+        hex("f8a98dbe11f6fe48cb0ce1818c454daf19e434a72d2038018898ae6b6a98b97a"),
+    );
+    assert!(ecdsa.verify(&q, &digest, &sig));
+
+    // secp224r1, SHA-256 (digest truncated to 224 bits)
+    let ecp: EcpA<BigInt> = EcpA::new("secp224r1");
+    let ecdsa = Ecdsa::new(&ecp);
+    assert_eq!(
+        ecdsa.bits2int(&digest),
+        hex("af2bdbe1aa9b6ec1e2ade1d694f41fc71a831d0268e9891562113d8a")
+    );
+    let q = ecp.mul(&ecp.get_gp(), &d);
+    assert_eq!(
+        q.x,
+        hex("549c4d8091b4fa9fd7bd82f109947a7afd4a4f5bc6f6f7066c7a989")
+    );
+    assert_eq!(
+        q.y,
+        hex("e13b016124d901cb46a163ffcb3c3b004e82127bc470590d04fd8f39")
+    );
+    let sig = Signature::new(
+        hex("e31bca174ac94706764b19020e67259819e487b5292459e0627253b1"),
+        hex("78ebf00ea4c7ab0f13e80e11d76e15202e72259db40f0ca42082b54c"),
+    );
+    assert!(ecdsa.verify(&q, &digest, &sig));
+}
+
+/// RFC 6979 Appendix A.2 test vectors
+#[test]
+fn rfc6979_test() {
+    println!("< ECDSA RFC 6979 Test >");
+
+    println!("> P-224");
+    // A.2.4: P-224
+    let ecp: EcpJ<BigInt> = EcpJ::new("secp224r1");
+    let x = hex("F220266E1105BFE3083E03EC7A3A654651F45E37167E88600BF257C1");
+    rfc6979_check::<Sha224>(
+        &ecp,
+        &x,
+        b"sample",
+        "1CDFE6662DDE1E4A1EC4CDEDF6A1F5A2FB7FBD9145C12113E6ABFD3E",
+        "A6694FD7718A21053F225D3F46197CA699D45006C06F871808F43EBC",
+    );
+    rfc6979_check::<Sha224>(
+        &ecp,
+        &x,
+        b"test",
+        "C441CE8E261DED634E4CF84910E4C5D1D22C5CF3B732BB204DBEF019",
+        "902F42847A63BDC5F6046ADA114953120F99442D76510150F372A3F4",
+    );
+    rfc6979_check::<Sha256>(
+        &ecp,
+        &x,
+        b"sample",
+        "61AA3DA010E8E8406C656BC477A7A7189895E7E840CDFE8FF42307BA",
+        "BC814050DAB5D23770879494F9E0A680DC1AF7161991BDE692B10101",
+    );
+    rfc6979_check::<Sha256>(
+        &ecp,
+        &x,
+        b"test",
+        "AD04DDE87B84747A243A631EA47A1BA6D1FAA059149AD2440DE6FBA6",
+        "178D49B1AE90E3D8B629BE3DB5683915F4E8C99FDF6E666CF37ADCFD",
+    );
+    rfc6979_check::<Sha384>(
+        &ecp,
+        &x,
+        b"sample",
+        "B115E5E36F0F9EC81F1325A5952878D745E19D7BB3EABFABA77E953",
+        "830F34CCDFE826CCFDC81EB4129772E20E122348A2BBD889A1B1AF1D",
+    );
+    rfc6979_check::<Sha384>(
+        &ecp,
+        &x,
+        b"test",
+        "389B92682E399B26518A95506B52C03BC9379A9DADF3391A21FB0EA4",
+        "414A718ED3249FF6DBC5B50C27F71F01F070944DA22AB1F78F559AAB",
+    );
+    rfc6979_check::<Sha512>(
+        &ecp,
+        &x,
+        b"sample",
+        "74BD1D979D5F32BF958DDC61E4FB4872ADCAFEB2256497CDAC30397",
+        "A4CECA196C3D5A1FF31027B33185DC8EE43F288B21AB342E5D8EB084",
+    );
+    rfc6979_check::<Sha512>(
+        &ecp,
+        &x,
+        b"test",
+        "49F050477C5ADD858CAC56208394B5A55BAEBBE887FDF765047C17C",
+        "77EB13E7005929CEFA3CD0403C7CDCC077ADF4E44F3C41B2F60ECFF",
+    );
+
+    println!("> P-384");
+    // A.2.6: P-384
+    let ecp: EcpJ<BigInt> = EcpJ::new("secp384r1");
+    let x = hex("6B9D3DAD2E1B8C1C05B19875B6659F4DE23C3B667BF297BA\
+         9AA47740787137D896D5724E4C70A825F872C9EA60D2EDF5");
+    rfc6979_check::<Sha224>(
+        &ecp,
+        &x,
+        b"sample",
+        "42356E76B55A6D9B4631C865445DBE54E056D3B3431766D0\
+         509244793C3F9366450F76EE3DE43F5A125333A6BE060122",
+        "9DA0C81787064021E78DF658F2FBB0B042BF304665DB721F\
+         077A4298B095E4834C082C03D83028EFBF93A3C23940CA8D",
+    );
+    rfc6979_check::<Sha224>(
+        &ecp,
+        &x,
+        b"test",
+        "E8C9D0B6EA72A0E7837FEA1D14A1A9557F29FAA45D3E7EE8\
+         88FC5BF954B5E62464A9A817C47FF78B8C11066B24080E72",
+        "7041D4A7A0379AC7232FF72E6F77B6DDB8F09B16CCE0EC3\
+         286B2BD43FA8C6141C53EA5ABEF0D8231077A04540A96B66",
+    );
+    rfc6979_check::<Sha256>(
+        &ecp,
+        &x,
+        b"sample",
+        "21B13D1E013C7FA1392D03C5F99AF8B30C570C6F98D4EA8E\
+         354B63A21D3DAA33BDE1E888E63355D92FA2B3C36D8FB2CD",
+        "F3AA443FB107745BF4BD77CB3891674632068A10CA67E3D4\
+         5DB2266FA7D1FEEBEFDC63ECCD1AC42EC0CB8668A4FA0AB0",
+    );
+    rfc6979_check::<Sha256>(
+        &ecp,
+        &x,
+        b"test",
+        "6D6DEFAC9AB64DABAFE36C6BF510352A4CC27001263638E5\
+         B16D9BB51D451559F918EEDAF2293BE5B475CC8F0188636B",
+        "2D46F3BECBCC523D5F1A1256BF0C9B024D879BA9E838144C\
+         8BA6BAEB4B53B47D51AB373F9845C0514EEFB14024787265",
+    );
+    rfc6979_check::<Sha384>(
+        &ecp,
+        &x,
+        b"sample",
+        "94EDBB92A5ECB8AAD4736E56C691916B3F88140666CE9FA7\
+         3D64C4EA95AD133C81A648152E44ACF96E36DD1E80FABE46",
+        "99EF4AEB15F178CEA1FE40DB2603138F130E740A19624526\
+         203B6351D0A3A94FA329C145786E679E7B82C71A38628AC8",
+    );
+    rfc6979_check::<Sha384>(
+        &ecp,
+        &x,
+        b"test",
+        "8203B63D3C853E8D77227FB377BCF7B7B772E97892A80F36\
+         AB775D509D7A5FEB0542A7F0812998DA8F1DD3CA3CF023DB",
+        "DDD0760448D42D8A43AF45AF836FCE4DE8BE06B485E9B61B\
+         827C2F13173923E06A739F040649A667BF3B828246BAA5A5",
+    );
+    rfc6979_check::<Sha512>(
+        &ecp,
+        &x,
+        b"sample",
+        "ED0959D5880AB2D869AE7F6C2915C6D60F96507F9CB3E047\
+         C0046861DA4A799CFE30F35CC900056D7C99CD7882433709",
+        "512C8CCEEE3890A84058CE1E22DBC2198F42323CE8ACA913\
+         5329F03C068E5112DC7CC3EF3446DEFCEB01A45C2667FDD5",
+    );
+    rfc6979_check::<Sha512>(
+        &ecp,
+        &x,
+        b"test",
+        "A0D5D090C9980FAF3C2CE57B7AE951D31977DD11C775D314\
+         AF55F76C676447D06FB6495CD21B4B6E340FC236584FB277",
+        "976984E59B4C77B0E8E4460DCA3D9F20E07B9BB1F63BEEFA\
+         F576F6B2E8B224634A2092CD3792E0159AD9CEE37659C736",
+    );
+
+    println!("> P-521");
+    // A.2.7: P-521
+    let ecp: EcpJ<BigInt> = EcpJ::new("P-521");
+    let x = hex(
+        "0FAD06DAA62BA3B25D2FB40133DA757205DE67F5BB0018FEE8C86E1B68C7E75CA\
+         A896EB32F1F47C70855836A6D16FCC1466F6D8FBEC67DB89EC0C08B0E996B83538",
+    );
+    rfc6979_check::<Sha224>(
+        &ecp,
+        &x,
+        b"sample",
+        "1776331CFCDF927D666E032E00CF776187BC9FDD8E69D0DABB4109FFE1B5E2A30\
+         715F4CC923A4A5E94D2503E9ACFED92857B7F31D7152E0F8C00C15FF3D87E2ED2E",
+        "50CB5265417FE2320BBB5A122B8E1A32BD699089851128E360E620A30C7E17BA4\
+         1A666AF126CE100E5799B153B60528D5300D08489CA9178FB610A2006C254B41F",
+    );
+    rfc6979_check::<Sha224>(
+        &ecp,
+        &x,
+        b"test",
+        "1C7ED902E123E6815546065A2C4AF977B22AA8EADDB68B2C1110E7EA44D42086B\
+         FE4A34B67DDC0E17E96536E358219B23A706C6A6E16BA77B65E1C595D43CAE17FB",
+        "177336676304FCB343CE028B38E7B4FBA76C1C1B277DA18CAD2A8478B2A9A9F5B\
+         EC0F3BA04F35DB3E4263569EC6AADE8C92746E4C82F8299AE1B8F1739F8FD519A4",
+    );
+    rfc6979_check::<Sha256>(
+        &ecp,
+        &x,
+        b"sample",
+        "1511BB4D675114FE266FC4372B87682BAECC01D3CC62CF2303C92B3526012659D\
+         16876E25C7C1E57648F23B73564D67F61C6F14D527D54972810421E7D87589E1A7",
+        "4A171143A83163D6DF460AAF61522695F207A58B95C0644D87E52AA1A347916E4\
+         F7A72930B1BC06DBE22CE3F58264AFD23704CBB63B29B931F7DE6C9D949A7ECFC",
+    );
+    rfc6979_check::<Sha256>(
+        &ecp,
+        &x,
+        b"test",
+        "E871C4A14F993C6C7369501900C4BC1E9C7B0B4BA44E04868B30B41D8071042E\
+         B28C4C250411D0CE08CD197E4188EA4876F279F90B3D8D74A3C76E6F1E4656AA8",
+        "CD52DBAA33B063C3A6CD8058A1FB0A46A4754B034FCC644766CA14DA8CA5CA9FD\
+         E00E88C1AD60CCBA759025299079D7A427EC3CC5B619BFBC828E7769BCD694E86",
+    );
+    rfc6979_check::<Sha384>(
+        &ecp,
+        &x,
+        b"sample",
+        "1EA842A0E17D2DE4F92C15315C63DDF72685C18195C2BB95E572B9C5136CA4B4B\
+         576AD712A52BE9730627D16054BA40CC0B8D3FF035B12AE75168397F5D50C67451",
+        "1F21A3CEE066E1961025FB048BD5FE2B7924D0CD797BABE0A83B66F1E35EEAF5F\
+         DE143FA85DC394A7DEE766523393784484BDF3E00114A1C857CDE1AA203DB65D61",
+    );
+    rfc6979_check::<Sha384>(
+        &ecp,
+        &x,
+        b"test",
+        "14BEE21A18B6D8B3C93FAB08D43E739707953244FDBE924FA926D76669E7AC8C8\
+         9DF62ED8975C2D8397A65A49DCC09F6B0AC62272741924D479354D74FF6075578C",
+        "133330865C067A0EAF72362A65E2D7BC4E461E8C8995C3B6226A21BD1AA78F0ED\
+         94FE536A0DCA35534F0CD1510C41525D163FE9D74D134881E35141ED5E8E95B979",
+    );
+    rfc6979_check::<Sha512>(
+        &ecp,
+        &x,
+        b"sample",
+        "C328FAFCBD79DD77850370C46325D987CB525569FB63C5D3BC53950E6D4C5F174\
+         E25A1EE9017B5D450606ADD152B534931D7D4E8455CC91F9B15BF05EC36E377FA",
+        "617CCE7CF5064806C467F678D3B4080D6F1CC50AF26CA209417308281B68AF282\
+         623EAA63E5B5C0723D8B8C37FF0777B1A20F8CCB1DCCC43997F1EE0E44DA4A67A",
+    );
+    rfc6979_check::<Sha512>(
+        &ecp,
+        &x,
+        b"test",
+        "13E99020ABF5CEE7525D16B69B229652AB6BDF2AFFCAEF38773B4B7D08725F10C\
+         DB93482FDCC54EDCEE91ECA4166B2A7C6265EF0CE2BD7051B7CEF945BABD47EE6D",
+        "1FBD0013C674AA79CB39849527916CE301C66EA7CE8B80682786AD60F98F7E78A\
+         19CA69EFF5C57400E3B3A0AD66CE0978214D13BAF4E9AC60752F7B155E2DE4DCE3",
+    );
+}
+
+fn rfc6979_check<D: Digest + BlockSizeUser>(
+    ecp: &impl EcOp<BigInt>,
+    x: &BigInt,
+    msg: &[u8],
+    r: &str,
+    s: &str,
+) {
+    let ecdsa = Ecdsa::new(ecp);
+    let digest = D::digest(msg);
+    let sig = ecdsa.sign_deterministic::<D>(x, &digest);
+    assert_eq!(sig.r(), &hex(r));
+    assert_eq!(sig.s(), &hex(s));
+
+    let q = ecp.mul(&ecp.get_gp(), x);
+    assert!(ecdsa.verify(&q, &digest, &sig));
+}
+
+#[test]
+fn recover_test() {
+    println!("< ECDSA Public Key Recovery Test >");
+    let digest = Sha256::digest(b"recover me");
+
+    for ecname in CURVES.iter() {
+        println!("> {}", ecname);
+        let ecj: EcpJ<BigInt> = EcpJ::new(ecname);
+        let ecdsa = Ecdsa::new(&ecj);
+        let (d, q) = ecdsa.gen_keypair();
+        for _ in 0..4 {
+            let (sig, recid) = ecdsa.sign_recoverable(&d, &digest);
+            assert!(ecdsa.verify(&q, &digest, &sig));
+            let q2 = ecdsa.recover_public_key(&digest, &sig, recid).unwrap();
+            assert!(ecj.equals(&q, &q2));
+            // the other y gives another key (or no key)
+            if let Ok(q3) = ecdsa.recover_public_key(&digest, &sig, recid ^ 1) {
+                assert!(!ecj.equals(&q, &q3));
+            }
+        }
+        let (sig, recid) = ecdsa.sign_deterministic_recoverable::<Sha256>(&d, &digest);
+        assert_eq!(sig, ecdsa.sign_deterministic::<Sha256>(&d, &digest));
+        let q2 = ecdsa.recover_public_key(&digest, &sig, recid).unwrap();
+        assert!(ecj.equals(&q, &q2));
+    }
+
+    // recovery id bit 1 (x1 >= n) is only possible when n < p
+    let ecj: EcpJ<BigInt> = EcpJ::new("secp256k1");
+    let ecdsa = Ecdsa::new(&ecj);
+    let (d, _) = ecdsa.gen_keypair();
+    let (sig, _) = ecdsa.sign_recoverable(&d, &digest);
+    assert_eq!(
+        ecdsa.recover_public_key(&digest, &sig, 2).err(),
+        Some(EcError::CoordinateOutOfRange)
+    );
+    assert_eq!(
+        ecdsa.recover_public_key(&digest, &sig, 4).err(),
+        Some(EcError::InvalidSignature)
+    );
+    let bad_sig = Signature::new(BigInt::from(0), sig.s().clone());
+    assert_eq!(
+        ecdsa.recover_public_key(&digest, &bad_sig, 0).err(),
+        Some(EcError::InvalidSignature)
+    );
+}
+
+/// secp256k1 recovery vectors (RustCrypto k256)
+#[test]
+fn recover_vector_test() {
+    println!("< ECDSA Public Key Recovery Known Answer Test >");
+    let ecj: EcpJ<BigInt> = EcpJ::new("secp256k1");
+    let ecdsa = Ecdsa::new(&ecj);
+    let digest = Sha256::digest(b"example message");
+
+    let vectors = [
+        (
+            "021a7a569e91dbf60581509c7fc946d1003b60c7dee85299538db6353538d59574",
+            "ce53abb3721bafc561408ce8ff99c909f7f0b18a2f788649d6470162ab1aa032",
+            "3971edc523a6d6453f3fb6128d318d9db1a5ff3386feb1047d9816e780039d52",
+            0,
+        ),
+        (
+            "036d6caac248af96f6afa7f904f550253a0f3ef3f5aa2fe6838a95b216691468e2",
+            "46c05b6368a44b8810d79859441d819b8e7cdc8bfd371e35c53196f4bcacdb51",
+            "35c7facce2a97b95eacba8a586d87b7958aaf8368ab29cee481f76e871dbd9cb",
+            1,
+        ),
+    ];
+    for (pk, r, s, recid) in vectors {
+        let sig = Signature::new(hex(r), hex(s));
+        let q = ecdsa.recover_public_key(&digest, &sig, recid).unwrap();
+        assert_eq!(ecj.get_encoded(&q), hex(pk).to_bytes_be().1);
+        assert!(ecdsa.verify(&q, &digest, &sig));
+    }
+
+    // Ethereum: Keccak-256 digest, RFC 6979 nonce with HMAC-SHA-256
+    let d = hex("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318");
+    let msg =
+        hex("e9808504e3b29200831e848094f0109fc8df283027b6285cc889f5aa624eac1f55843b9aca0080018080")
+            .to_bytes_be()
+            .1;
+    let digest = Keccak256::digest(&msg);
+    let (sig, recid) = ecdsa.sign_deterministic_recoverable::<Sha256>(&d, &digest);
+    // low s (EIP-2):  (r, n - s) is the signature of -R,  flip the y parity
+    let n = ecj.get_order();
+    let (sig, recid) = if sig.s() > &(&n >> 1) {
+        (Signature::new(sig.r().clone(), &n - sig.s()), recid ^ 1)
+    } else {
+        (sig, recid)
+    };
+    assert_eq!(
+        sig,
+        Signature::new(
+            hex("c9cf86333bcb065d140032ecaab5d9281bde80f21b9687b3e94161de42d51895"),
+            hex("727a108a0b8d101465414033c3f705a9c7b826e596766046ee1183dbc8aeaa68")
+        )
+    );
+    assert_eq!(recid, 0);
+    let q = ecdsa.recover_public_key(&digest, &sig, recid).unwrap();
+    assert!(ecj.equals(&q, &ecj.mul(&ecj.get_gp(), &d)));
+}
+
+#[test]
+fn verify_batch_test() {
+    println!("< ECDSA Batch Verification Test >");
+    for ecname in CURVES.iter() {
+        println!("> {}", ecname);
+        let ecj: EcpJ<BigInt> = EcpJ::new(ecname);
+        do_verify_batch(&ecj);
+    }
+    let eca: EcpA<BigInt> = EcpA::new("secp256k1");
+    do_verify_batch(&eca);
+}
+
+/// (Q, digest, signature, recovery id)
+type BatchItem = (Point<BigInt>, Vec<u8>, Signature<BigInt>, u8);
+
+fn do_verify_batch(ecp: &impl EcOp<BigInt>) {
+    let ecdsa = Ecdsa::new(ecp);
+    let n = ecp.get_order();
+
+    // a few keys, many signatures
+    let keys: Vec<(BigInt, Point<BigInt>)> = (0..3).map(|_| ecdsa.gen_keypair()).collect();
+    let digests: Vec<Vec<u8>> = (0..12u32)
+        .map(|i| Sha256::digest(i.to_be_bytes()).to_vec())
+        .collect();
+    let mut sigs: Vec<(Signature<BigInt>, u8)> = digests
+        .iter()
+        .enumerate()
+        .map(|(i, digest)| ecdsa.sign_recoverable(&keys[i % 3].0, digest))
+        .collect();
+    let items = |sigs: &[(Signature<BigInt>, u8)]| -> Vec<BatchItem> {
+        sigs.iter()
+            .enumerate()
+            .map(|(i, (sig, recid))| {
+                (
+                    keys[i % 3].1.clone(),
+                    digests[i].clone(),
+                    sig.clone(),
+                    *recid,
+                )
+            })
+            .collect()
+    };
+    let batch = |items: &[BatchItem]| {
+        let refs: Vec<_> = items
+            .iter()
+            .map(|(q, digest, sig, recid)| (q, &digest[..], sig, *recid))
+            .collect();
+        ecdsa.verify_batch(&refs)
+    };
+    assert_eq!(batch(&items(&sigs)), Ok(()));
+    assert_eq!(batch(&[]), Ok(()));
+
+    // tampered s, wrong recovery id, r out of range
+    let (sig, recid) = sigs[2].clone();
+    sigs[2] = (
+        Signature::new(sig.r().clone(), (sig.s() + BigInt::one()) % &n),
+        recid,
+    );
+    sigs[7].1 ^= 1;
+    sigs[11].0 = Signature::new(n.clone(), sigs[11].0.s().clone());
+    let mut bad = items(&sigs);
+    assert_eq!(batch(&bad), Err(vec![2, 7, 11]));
+    for i in [2, 11] {
+        assert!(!ecdsa.verify(&bad[i].0, &bad[i].1, &bad[i].2));
+    }
+    assert!(ecdsa.verify(&bad[7].0, &bad[7].1, &bad[7].2));
+
+    // wrong key
+    bad[4].0 = keys[0].1.clone();
+    assert_eq!(batch(&bad), Err(vec![2, 4, 7, 11]));
+}
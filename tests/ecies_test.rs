@@ -0,0 +1,201 @@
+//!
+//! ECIES Test Program
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+extern crate ecc;
+
+use num_bigint::BigInt;
+use sha2::{Sha256, Sha512};
+
+use ecc::ec_param::EcParam;
+use ecc::ecc_a::EcpA;
+use ecc::ecc_j::EcpJ;
+use ecc::ecies::{Cipher, Ecies, EciesError, Kdf, MacScheme};
+use ecc::fixint::Fix256;
+use ecc::number::Number;
+use ecc::{EcError, EcOp, PointFormat};
+
+fn hex(s: &str) -> BigInt {
+    let s: String = s.split_whitespace().collect();
+    BigInt::from_bytes_radix(s.as_bytes(), 16)
+}
+
+fn unhex(s: &str) -> Vec<u8> {
+    let s: String = s.split_whitespace().collect();
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+const MSG: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+#[test]
+fn ecies_test() {
+    println!("< ECIES Test >");
+    for ecname in EcParam::curve_ids() {
+        println!("> {}", ecname);
+        let ecj: EcpJ<BigInt> = EcpJ::new(ecname);
+        do_round_trip(&ecj);
+    }
+    let eca: EcpA<BigInt> = EcpA::new("secp160r1");
+    do_round_trip(&eca);
+    let ecj: EcpJ<Fix256> = EcpJ::new("secp256k1");
+    do_round_trip(&ecj);
+}
+
+fn do_round_trip<T: Number>(ecp: &impl EcOp<T>) {
+    let ln = ecp.get_field_len();
+    let configs = [
+        (
+            Kdf::X963,
+            Cipher::Aes128Ctr,
+            MacScheme::Hmac,
+            PointFormat::Uncompressed,
+        ),
+        (
+            Kdf::Hkdf,
+            Cipher::Aes256Ctr,
+            MacScheme::HmacHalf,
+            PointFormat::Compressed,
+        ),
+        (
+            Kdf::X963,
+            Cipher::Xor,
+            MacScheme::HmacHalf,
+            PointFormat::Hybrid,
+        ),
+    ];
+    for (kdf, cipher, mac, format) in configs {
+        let ecies: Ecies<T, _, Sha256> = Ecies::new(ecp)
+            .with_kdf(kdf)
+            .with_cipher(cipher)
+            .with_mac(mac)
+            .with_format(format)
+            .with_shared_info(b"info1", b"info2");
+        let (d, q) = ecies.gen_keypair();
+        for msg in [&b""[..], MSG] {
+            let c = ecies.encrypt(&q, msg).unwrap();
+            let rlen = if format == PointFormat::Compressed {
+                ln + 1
+            } else {
+                2 * ln + 1
+            };
+            assert_eq!(c.len(), rlen + msg.len() + ecies.get_tag_len());
+            assert_eq!(ecies.decrypt(&d, &c).unwrap(), msg);
+        }
+    }
+    // ECIES-KEM
+    let ecies: Ecies<T, _, Sha512> = Ecies::new(ecp);
+    let (d, q) = ecies.gen_keypair();
+    let (c0, key) = ecies.kem_encap(&q, 80).unwrap();
+    assert_eq!(key.len(), 80);
+    assert_eq!(ecies.kem_decap(&d, &c0, 80).unwrap(), key);
+}
+
+#[test]
+fn ecies_kat_test() {
+    println!("< ECIES Known Answer Test (P-256, SHA-256) >");
+    let ecj: EcpJ<BigInt> = EcpJ::new("secp256r1");
+    let d = hex("C9AFA9D845BA75166B5C215767B1D6934E50C3DB36E89B127B8A622B120F6721");
+    let k = hex("0123456789ABCDEF0123456789ABCDEF0123456789ABCDEF0123456789ABCDEF");
+    let q = ecj.mul(&ecj.get_gp(), &d);
+
+    // X9.63 KDF, AES-128-CTR, HMAC-SHA-256, uncompressed R, SharedInfo1 / 2
+    let ecies: Ecies<BigInt, _, Sha256> = Ecies::new(&ecj).with_shared_info(b"info1", b"info2");
+    let c = unhex(
+        "04d8cd12ea5c67f2f8a00c1124893edcfa6754c4d6cede6be13bdf2295c810a97f
+         a5a89d2d2a360c0ca9a4d6c7c9ed4b28d3e199d6627f2e696d689c310a5b0f48
+         e26d5292aff0d1f5654e7b81dd15790e98a1f135dae1cfa5cbd3839fc76d368d
+         0aee7aa3ef528e99d3a2bb8868e470c2059f3a11dc8478f183dd8e343d631696
+         8938b7d6e970a769c43c1d",
+    );
+    assert_eq!(ecies.encrypt_with_k(&q, MSG, &k).unwrap(), c);
+    assert_eq!(ecies.decrypt(&d, &c).unwrap(), MSG);
+
+    // HKDF, XOR, HMAC-SHA-256-128, compressed R
+    let ecies: Ecies<BigInt, _, Sha256> = Ecies::new(&ecj)
+        .with_kdf(Kdf::Hkdf)
+        .with_cipher(Cipher::Xor)
+        .with_mac(MacScheme::HmacHalf)
+        .with_format(PointFormat::Compressed);
+    let c = unhex(
+        "02d8cd12ea5c67f2f8a00c1124893edcfa6754c4d6cede6be13bdf2295c810a97f
+         081a3aac21a0d3e4eeb015f2cf489965dbef603366917ad0e8fdab7ceacaac35
+         66d2a4ca5564859ecd0b7ea174193e51693ffe1cc9f28364265219",
+    );
+    assert_eq!(ecies.encrypt_with_k(&q, MSG, &k).unwrap(), c);
+    assert_eq!(ecies.decrypt(&d, &c).unwrap(), MSG);
+
+    // ECIES-KEM:  K = KDF2(C0 || PEH)
+    let ecies: Ecies<BigInt, _, Sha256> = Ecies::new(&ecj);
+    let (c0, key) = ecies.kem_encap_with_k(&q, 32, &k).unwrap();
+    assert_eq!(
+        key,
+        unhex("5aa0e8429e02bef94f670d45c573ec40ffb138adf7b0fe512d741803fb9fea11")
+    );
+    assert_eq!(ecies.kem_decap(&d, &c0, 32).unwrap(), key);
+}
+
+#[test]
+fn ecies_error_test() {
+    println!("< ECIES Error Test >");
+    let ecj: EcpJ<BigInt> = EcpJ::new("secp256r1");
+    let ecies: Ecies<BigInt, _, Sha256> = Ecies::new(&ecj);
+    let (d, q) = ecies.gen_keypair();
+    let c = ecies.encrypt(&q, MSG).unwrap();
+
+    // modified ephemeral point / message / tag
+    for i in [1, 70, c.len() - 1] {
+        let mut bad = c.clone();
+        bad[i] ^= 0x01;
+        assert!(ecies.decrypt(&d, &bad).is_err());
+    }
+    let mut bad = c.clone();
+    bad[70] ^= 0x01;
+    assert_eq!(ecies.decrypt(&d, &bad), Err(EciesError::MacMismatch));
+
+    // wrong private key
+    let (d2, _) = ecies.gen_keypair();
+    assert_eq!(ecies.decrypt(&d2, &c), Err(EciesError::MacMismatch));
+
+    // truncated / bad prefix / point at infinity
+    assert_eq!(
+        ecies.decrypt(&d, &c[..80]),
+        Err(EciesError::InvalidLength(80))
+    );
+    assert_eq!(ecies.decrypt(&d, &[]), Err(EciesError::InvalidLength(0)));
+    let mut bad = c.clone();
+    bad[0] = 0x05;
+    assert_eq!(
+        ecies.decrypt(&d, &bad),
+        Err(EciesError::Point(EcError::InvalidPrefix(0x05)))
+    );
+    assert_eq!(
+        ecies.kem_decap(&d, &[0x00], 32),
+        Err(EciesError::Point(EcError::InvalidPrefix(0x00)))
+    );
+
+    // invalid public key
+    let mut q2 = q.clone();
+    q2.y += 1;
+    assert_eq!(
+        ecies.encrypt(&q2, MSG),
+        Err(EciesError::Point(EcError::NotOnCurve))
+    );
+
+    // X9.63 output limit ((2^32 - 1) * hash length)
+    assert_eq!(
+        ecies.kem_encap(&q, 32 * (u32::MAX as usize) + 1),
+        Err(EciesError::KdfLength)
+    );
+
+    // HKDF output limit (255 * hash length)
+    let ecies = ecies.with_kdf(Kdf::Hkdf).with_cipher(Cipher::Xor);
+    assert_eq!(ecies.encrypt(&q, &[0; 8192]), Err(EciesError::KdfLength));
+    assert_eq!(
+        ecies.kem_encap(&q, 255 * 32 + 1),
+        Err(EciesError::KdfLength)
+    );
+}
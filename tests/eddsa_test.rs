@@ -0,0 +1,187 @@
+//!
+//! EdDSA (RFC 8032) Test Program
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+extern crate ecc;
+
+use ibig::IBig;
+use num_bigint::BigInt;
+use sha2::{Digest, Sha512};
+
+use ecc::ecc_e::EcpE;
+use ecc::eddsa::EdDsa;
+use ecc::fixint::{Fix256, Fix521};
+use ecc::number::Number;
+use ecc::{EcError, EcOp};
+
+/// RFC 8032 test vectors (7.1 - 7.5)
+const VECTORS: &str = include_str!("data/rfc8032_test_vectors.csv");
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn rfc8032_vectors_test() {
+    println!("< RFC 8032 Test Vectors >");
+    let ece: EcpE<BigInt> = EcpE::new("edwards25519");
+    do_vectors_test(&ece, "Ed25519");
+    let ece: EcpE<Fix256> = EcpE::new("edwards25519");
+    do_vectors_test(&ece, "Ed25519");
+    let ece: EcpE<IBig> = EcpE::new("edwards25519");
+    do_vectors_test(&ece, "Ed25519");
+    let ece: EcpE<BigInt> = EcpE::new("edwards448");
+    do_vectors_test(&ece, "Ed448");
+    let ece: EcpE<Fix521> = EcpE::new("edwards448");
+    do_vectors_test(&ece, "Ed448");
+}
+
+fn do_vectors_test<T: Number>(ece: &EcpE<T>, scheme: &str) {
+    let eddsa = EdDsa::new(ece);
+
+    for line in VECTORS.lines().skip(1) {
+        let v: Vec<&str> = line.split(',').collect();
+        if v[0] != scheme {
+            continue;
+        }
+        println!("> {} {}", v[0], v[7]);
+        let (sk, pk, ctx) = (unhex(v[2]), unhex(v[3]), unhex(v[4]));
+        let (msg, sig) = (unhex(v[5]), unhex(v[6]));
+
+        assert_eq!(eddsa.public_key(&sk).unwrap(), pk, "public key {}", v[7]);
+        let (s, ok) = match v[1] {
+            "pure" => (
+                eddsa.sign(&sk, &msg).unwrap(),
+                eddsa.verify(&pk, &msg, &sig),
+            ),
+            "ctx" => (
+                eddsa.sign_ctx(&sk, &msg, &ctx).unwrap(),
+                eddsa.verify_ctx(&pk, &msg, &ctx, &sig),
+            ),
+            _ => (
+                eddsa.sign_ph(&sk, &msg, &ctx).unwrap(),
+                eddsa.verify_ph(&pk, &msg, &ctx, &sig),
+            ),
+        };
+        assert_eq!(s, sig, "signature {}", v[7]);
+        assert!(ok, "verify {}", v[7]);
+    }
+}
+
+#[test]
+fn eddsa_test() {
+    println!("< EdDSA Test >");
+    for ecname in ["Ed25519", "Ed448"] {
+        println!("> {}", ecname);
+        let ece: EcpE<BigInt> = EcpE::new(ecname);
+        do_sign_verify(&ece);
+    }
+}
+
+fn do_sign_verify(ece: &EcpE<BigInt>) {
+    let eddsa = EdDsa::new(ece);
+    let len = eddsa.get_len();
+    let msg = b"EdDSA test message";
+
+    let (sk, pk) = eddsa.gen_keypair();
+    assert_eq!((sk.len(), pk.len()), (len, len));
+    assert_eq!(eddsa.public_key(&sk).unwrap(), pk);
+
+    let sig = eddsa.sign(&sk, msg).unwrap();
+    let sig_ctx = eddsa.sign_ctx(&sk, msg, b"foo").unwrap();
+    let sig_ph = eddsa.sign_ph(&sk, msg, b"").unwrap();
+    assert_eq!(sig.len(), 2 * len);
+    assert!(eddsa.verify(&pk, msg, &sig));
+    assert!(eddsa.verify_ctx(&pk, msg, b"foo", &sig_ctx));
+    assert!(eddsa.verify_ph(&pk, msg, b"", &sig_ph));
+    // deterministic
+    assert_eq!(eddsa.sign(&sk, msg).unwrap(), sig);
+
+    // the variants are separated
+    assert!(!eddsa.verify_ctx(&pk, msg, b"bar", &sig_ctx));
+    assert!(!eddsa.verify(&pk, msg, &sig_ctx));
+    assert!(!eddsa.verify_ph(&pk, msg, b"foo", &sig_ctx));
+    assert!(!eddsa.verify(&pk, msg, &sig_ph));
+    assert!(!eddsa.verify_ctx(&pk, msg, b"", &sig_ph));
+
+    // tampered message / signature / key
+    assert!(!eddsa.verify(&pk, b"EdDSA test messagf", &sig));
+    for i in [0, len - 1, len, 2 * len - 1] {
+        let mut bad = sig.clone();
+        bad[i] ^= 0x01;
+        assert!(!eddsa.verify(&pk, msg, &bad), "{}", i);
+    }
+    let (_, pk2) = eddsa.gen_keypair();
+    assert!(!eddsa.verify(&pk2, msg, &sig));
+    assert!(!eddsa.verify(&pk, msg, &sig[..2 * len - 1]));
+    assert!(!eddsa.verify(&pk[..len - 1], msg, &sig));
+
+    // S + L (non-canonical S) is rejected
+    let n = ece.get_order();
+    let mut s_le = sig[len..].to_vec();
+    s_le.reverse();
+    let s2 = <BigInt as Number>::from_bytes_be(&s_le) + &n;
+    let mut s2 = s2.to_bytes_be_pad(len);
+    s2.reverse();
+    let mut bad = sig[..len].to_vec();
+    bad.extend_from_slice(&s2);
+    assert!(!eddsa.verify(&pk, msg, &bad));
+
+    // invalid lengths
+    assert_eq!(
+        eddsa.sign(&sk[1..], msg),
+        Err(EcError::InvalidLength(len - 1))
+    );
+    assert_eq!(eddsa.public_key(&[]), Err(EcError::InvalidLength(0)));
+    let ctx = [0u8; 256];
+    assert_eq!(
+        eddsa.sign_ctx(&sk, msg, &ctx),
+        Err(EcError::InvalidLength(256))
+    );
+    assert!(!eddsa.verify_ctx(&pk, msg, &ctx, &sig_ctx));
+}
+
+/// Ed25519ctx rejects an empty context (RFC 8032, 8.3),  Ed448 accepts it
+#[test]
+fn empty_context_test() {
+    // key of the RFC 8032 Ed25519ctx vectors (7.2)
+    let ece: EcpE<BigInt> = EcpE::new("Ed25519");
+    let eddsa = EdDsa::new(&ece);
+    let sk = unhex("0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6");
+    let pk = unhex("dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292");
+    let msg = unhex("f726936d19c800494e3fdaff20b276a8");
+    assert_eq!(
+        eddsa.sign_ctx(&sk, &msg, b""),
+        Err(EcError::InvalidLength(0))
+    );
+    let sig = eddsa.sign(&sk, &msg).unwrap();
+    assert!(eddsa.verify(&pk, &msg, &sig));
+    assert!(!eddsa.verify_ctx(&pk, &msg, b"", &sig));
+    // Ed25519ph keeps the empty context
+    let sig_ph = eddsa.sign_ph(&sk, &msg, b"").unwrap();
+    assert!(eddsa.verify_ph(&pk, &msg, b"", &sig_ph));
+
+    // Ed448 with an empty context is plain Ed448
+    let ece: EcpE<BigInt> = EcpE::new("Ed448");
+    let eddsa = EdDsa::new(&ece);
+    let (sk, pk) = eddsa.gen_keypair();
+    let sig = eddsa.sign_ctx(&sk, &msg, b"").unwrap();
+    assert_eq!(sig, eddsa.sign(&sk, &msg).unwrap());
+    assert!(eddsa.verify_ctx(&pk, &msg, b"", &sig));
+}
+
+/// Ed25519ph signs SHA-512(M) under dom2(1, ctx)
+#[test]
+fn prehash_test() {
+    let ece: EcpE<BigInt> = EcpE::new("edwards25519");
+    let eddsa = EdDsa::new(&ece);
+    let (sk, pk) = eddsa.gen_keypair();
+    let sig = eddsa.sign_ph(&sk, b"abc", b"").unwrap();
+    assert!(eddsa.verify_ph(&pk, b"abc", b"", &sig));
+    // not a pure signature of the digest
+    assert!(!eddsa.verify(&pk, &Sha512::digest(b"abc"), &sig));
+}
@@ -0,0 +1,106 @@
+//!
+//! Prime Field Arithmetic Test Program
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+extern crate ecc;
+
+use ibig::IBig;
+use num_bigint::BigInt;
+
+use ecc::ec_param::{EcParam, ParamOp};
+use ecc::ecc_a::EcpA;
+use ecc::ecc_j::EcpJ;
+use ecc::field::PrimeField;
+use ecc::fixint::Fix521;
+use ecc::number::Number;
+use ecc::EcOp;
+
+// p = 3 mod 4, p = 1 mod 4 (secp224r1: 2-adicity 96), special-form and generic primes
+const CURVES: [&str; 5] = [
+    "secp112r1",
+    "secp224r1",
+    "secp256k1",
+    "brainpoolP256r1",
+    "secp521r1",
+];
+
+#[test]
+fn field_test() {
+    println!("< Prime Field Test >");
+    for ecname in CURVES.iter() {
+        println!("> {}", ecname);
+        let p: BigInt = EcParam::new(ecname).get_prime();
+        do_field_test(&PrimeField::new(&p));
+        do_field_test(&PrimeField::for_prime(&p));
+        let p: IBig = EcParam::new(ecname).get_prime();
+        do_field_test(&PrimeField::for_prime(&p));
+        let p: Fix521 = EcParam::new(ecname).get_prime();
+        do_field_test(&PrimeField::for_prime(&p));
+    }
+}
+
+fn do_field_test<T: Number>(f: &PrimeField<T>) {
+    let p = f.get_prime();
+    let md = |x: T| Number::mod_cal(&x, p);
+
+    assert!(f.zero().is_zero() && f.one().is_one());
+    assert!(f.elem(p).is_zero());
+    assert!(f.elem(&T::zero().sub_ref(&T::one())).value() == p.sub_ref(&T::one()));
+    assert!((-f.zero()).is_zero());
+    assert!(f.zero().inv().is_none());
+
+    for _ in 0..10 {
+        let (a, b) = (T::gen_rand(&T::zero(), p), T::gen_rand(&T::zero(), p));
+        let (fa, fb) = (f.elem(&a), f.elem(&b));
+
+        assert!((&fa + &fb).value() == md(a.add_ref(&b)));
+        assert!((&fa - &fb).value() == md(a.sub_ref(&b)));
+        assert!((&fa * &fb).value() == md(a.mul_ref(&b)));
+        assert!((-&fa).value() == md(T::zero().sub_ref(&a)));
+        assert!(fa.square() == &fa * &fa);
+        assert!(fa.double() == &fa + &fa);
+        assert!(fa.half().double() == fa);
+        assert!(fa.pow(&T::from(3u32)) == &fa.square() * &fa);
+        assert!(fa.pow(&p.sub_ref(&T::one())).is_one());
+        if let Some(ia) = fa.inv() {
+            assert!((&ia * &fa).is_one());
+        }
+
+        // sqrt: squares have a root, -square is a non-residue iff p = 3 mod 4
+        let sq = fa.square();
+        let r = sq.sqrt().unwrap();
+        assert!(r == fa || r == -&fa);
+        if p.test_bit(1) && !sq.is_zero() {
+            assert!((-sq).sqrt().is_none());
+        }
+    }
+}
+
+/// point_from_x / gen_point (field square root) on every backend
+#[test]
+fn field_ec_test() {
+    println!("< Prime Field EC Test >");
+    for ecname in CURVES.iter() {
+        println!("> {}", ecname);
+        do_ec_test(&EcpA::<IBig>::new(ecname));
+        do_ec_test(&EcpJ::<IBig>::new(ecname));
+        do_ec_test(&EcpJ::<BigInt>::new(ecname));
+        do_ec_test(&EcpJ::<Fix521>::new(ecname));
+    }
+}
+
+fn do_ec_test<T: Number>(ecp: &impl EcOp<T>) {
+    let p_g = ecp.get_gp();
+    let p_p = ecp.point_from_x(&p_g.x, 0).unwrap();
+    let p_q = ecp.point_from_x(&p_g.x, 1).unwrap();
+    assert!(!p_p.y.test_bit(0) && p_q.y.test_bit(0));
+    assert!(ecp.equals(&p_p, &p_g) || ecp.equals(&p_q, &p_g));
+    assert!(ecp.equals(&ecp.negate(&p_p), &p_q));
+
+    let p_r = ecp.gen_point();
+    assert!(ecp.on_curve(&p_r));
+    let p_s = ecp.to_affine(&ecp.add(&p_r, &p_g));
+    assert!(ecp.on_curve(&p_s));
+    assert!(ecp.equals(&ecp.add(&p_s, &ecp.negate(&p_g)), &p_r));
+}
@@ -0,0 +1,161 @@
+//!
+//! Fixed-width Integer Test Program
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+extern crate ecc;
+
+use num_bigint::{BigInt, RandBigInt};
+use num_traits::{One, Zero};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+use ecc::ecc_a::EcpA;
+use ecc::ecc_j::EcpJ;
+use ecc::ecdh::Ecdh;
+use ecc::ecdsa::Ecdsa;
+use ecc::fixint::{Fix256, Fix384, Fix521, FixInt};
+use ecc::number::Number;
+use ecc::EcOp;
+
+fn fix<const L: usize>(x: &BigInt) -> FixInt<L> {
+    FixInt::from_bytes_radix(x.to_str_radix(16).as_bytes(), 16)
+}
+
+fn big<const L: usize>(x: &FixInt<L>) -> BigInt {
+    BigInt::parse_bytes(x.to_hex().as_bytes(), 16).unwrap()
+}
+
+fn rand_signed(bits: u64) -> BigInt {
+    let mut rng = rand::thread_rng();
+    let b = rng.gen_range(1..=bits);
+    let x = BigInt::from(rng.gen_biguint(b));
+    if rng.gen_bool(0.5) {
+        -x
+    } else {
+        x
+    }
+}
+
+#[test]
+fn fixint_arith_test() {
+    println!("< FixInt Arithmetic Test >");
+    for _ in 0..2000 {
+        let (a, b) = (rand_signed(400), rand_signed(400));
+        let (fa, fb): (Fix256, Fix256) = (fix(&a), fix(&b));
+        assert_eq!(big(&fa), a);
+        assert_eq!(big(&(fa + fb)), &a + &b);
+        assert_eq!(big(&(fa - fb)), &a - &b);
+        assert_eq!(big(&(fa * fb)), &a * &b);
+        assert_eq!(fa < fb, a < b);
+        assert_eq!(fa == fb, a == b);
+        if !b.is_zero() {
+            // truncated division
+            assert_eq!(big(&(fa / fb)), &a / &b);
+            assert_eq!(big(&(fa % fb)), &a % &b);
+        }
+        let k = rand::thread_rng().gen_range(0..450);
+        assert_eq!(big(&(fa >> k)), &a >> k);
+        assert_eq!(fa.bit_len(), a.bits() as usize);
+        assert_eq!(fa.to_string(), a.to_string());
+    }
+    // small divisor, quotient digit corrections
+    let m: BigInt = (BigInt::one() << 256) - 1;
+    for d in [
+        BigInt::from(3),
+        BigInt::one() << 64,
+        (BigInt::one() << 128) - 1,
+        m.clone() >> 1,
+    ] {
+        let (fm, fd): (Fix256, Fix256) = (fix(&m), fix(&d));
+        assert_eq!(big(&(fm / fd)), &m / &d);
+        assert_eq!(big(&(fm % fd)), &m % &d);
+    }
+
+    println!("> overflow");
+    let max: Fix256 = fix(&((BigInt::one() << 832) - 1));
+    assert!(std::panic::catch_unwind(|| max + Fix256::one()).is_err());
+    assert!(std::panic::catch_unwind(|| max * Fix256::from(2u32)).is_err());
+    assert!(std::panic::catch_unwind(|| Fix256::one() / Fix256::zero()).is_err());
+}
+
+#[test]
+fn fixint_number_test() {
+    println!("< FixInt Number Test >");
+    let p = BigInt::parse_bytes(
+        b"FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFF",
+        16,
+    )
+    .unwrap();
+    let fp: Fix256 = fix(&p);
+    let mut rng = rand::thread_rng();
+    for _ in 0..50 {
+        let a = rng.gen_bigint_range(&BigInt::one(), &p);
+        let e = rng.gen_bigint_range(&BigInt::zero(), &p);
+        let fa: Fix256 = fix(&a);
+        assert_eq!(big(&Number::mod_pow(&fa, &fix(&e), &fp)), a.modpow(&e, &p));
+        assert_eq!(big(&Number::mod_inv(&fa, &fp)), Number::mod_inv(&a, &p));
+        assert_eq!(fa.jacobi(&fp), a.jacobi(&p));
+        assert_eq!(Number::mod_cal(&(-fa), &fp), fp - fa);
+        if a.jacobi(&p) == 1 {
+            let r = Number::mod_sqrt(&fa, &fp).unwrap();
+            assert_eq!(r * r % fp, fa);
+        } else {
+            assert!(Number::mod_sqrt(&fa, &fp).is_none());
+        }
+        let ab = a.to_bytes_be().1;
+        assert_eq!(Fix256::from_bytes_be(&ab).to_bytes_be(), ab);
+        assert_eq!(fa.to_bytes_be_pad(40), a.to_bytes_be_pad(40));
+        assert_eq!(fa.to_hex(), a.to_hex());
+        let r = Fix256::gen_rand(&fa, &fp);
+        assert!(fa <= r && r < fp);
+    }
+    assert_eq!(Fix256::from(3u32).pow(5), Fix256::from(243u32));
+    assert_eq!(Fix256::zero().to_bytes_be(), [0]);
+    assert_eq!(format!("{:X}", fp), format!("{:X}", p));
+}
+
+#[test]
+fn fixint_ec_test() {
+    println!("< FixInt EC Test >");
+    for ecname in ["secp256k1", "secp256r1", "brainpoolP256r1"] {
+        println!("> {}", ecname);
+        let eca: EcpA<Fix256> = EcpA::new(ecname);
+        do_ec_test(&eca, &EcpJ::<BigInt>::new(ecname));
+        let ecj: EcpJ<Fix256> = EcpJ::new(ecname);
+        do_ec_test(&ecj, &EcpJ::<BigInt>::new(ecname));
+    }
+    println!("> secp384r1");
+    let ecj: EcpJ<Fix384> = EcpJ::new("secp384r1");
+    do_ec_test(&ecj, &EcpJ::<BigInt>::new("secp384r1"));
+    println!("> secp521r1");
+    let ecj: EcpJ<Fix521> = EcpJ::new("secp521r1");
+    do_ec_test(&ecj, &EcpJ::<BigInt>::new("secp521r1"));
+}
+
+fn do_ec_test<const L: usize>(ecp: &impl EcOp<FixInt<L>>, ecb: &impl EcOp<BigInt>) {
+    let p_g = ecp.get_gp();
+    assert!(ecp.check_point(&p_g).is_ok());
+    assert!(ecp.is_zero(&ecp.mul(&p_g, &ecp.get_order())));
+
+    let k = rand::thread_rng().gen_bigint_range(&BigInt::one(), &ecb.get_order());
+    let p_q = ecp.to_affine(&ecp.mul(&p_g, &fix(&k)));
+    let p_r = ecb.to_affine(&ecb.mul(&ecb.get_gp(), &k));
+    assert_eq!(big(&p_q.x), p_r.x);
+    assert_eq!(big(&p_q.y), p_r.y);
+
+    let enc = ecp.get_encoded(&p_q);
+    assert_eq!(enc, ecb.get_encoded(&p_r));
+    assert!(ecp.equals(&ecp.decode_point(&enc), &p_q));
+
+    let ecdsa = Ecdsa::new(ecp);
+    let (d, q) = ecdsa.gen_keypair();
+    let digest = Sha256::digest(b"sample");
+    let sig = ecdsa.sign_deterministic::<Sha256>(&d, &digest);
+    assert!(ecdsa.verify(&q, &digest, &sig));
+
+    let ecdh = Ecdh::new(ecp);
+    let (da, qa) = ecdh.gen_keypair();
+    let (db, qb) = ecdh.gen_keypair();
+    assert_eq!(ecdh.derive_point(&da, &qb), ecdh.derive_point(&db, &qa));
+}
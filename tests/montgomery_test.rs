@@ -0,0 +1,90 @@
+//!
+//! Montgomery Arithmetic Test Program
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+extern crate ecc;
+
+use ibig::IBig;
+use num_bigint::BigInt;
+
+use ecc::ec_param::{EcParam, ParamOp};
+use ecc::ecc_a::EcpA;
+use ecc::ecc_j::EcpJ;
+use ecc::fixint::Fix521;
+use ecc::montgomery::Montgomery;
+use ecc::number::Number;
+use ecc::EcOp;
+
+const CURVES: [&str; 5] = [
+    "secp112r1",
+    "secp192k1",
+    "secp256r1",
+    "brainpoolP320r1",
+    "secp521r1",
+];
+
+#[test]
+fn montgomery_test() {
+    println!("< Montgomery Arithmetic Test >");
+    for ecname in CURVES.iter() {
+        println!("> {}", ecname);
+        let param = EcParam::new(ecname);
+        do_montgomery_test::<BigInt>(&param.get_prime());
+        do_montgomery_test::<IBig>(&param.get_prime());
+        do_montgomery_test::<Fix521>(&param.get_prime());
+    }
+}
+
+fn do_montgomery_test<T: Number>(p: &T) {
+    let m = Montgomery::new(p);
+    assert!(m.from_mont(m.one()) == T::one());
+    assert!(m.to_mont(&T::zero()) == T::zero());
+    assert!(m.to_mont(p) == T::zero());
+
+    for _ in 0..20 {
+        let a = T::gen_rand(&T::zero(), p);
+        let b = T::gen_rand(&T::zero(), p);
+        let (am, bm) = (m.to_mont(&a), m.to_mont(&b));
+        assert!(am < *p);
+        assert!(m.from_mont(&am) == a);
+        assert!(m.from_mont(&m.mul(&am, &bm)) == a.mul_ref(&b) % p);
+        assert!(m.from_mont(&m.sqr(&am)) == a.mul_ref(&a) % p);
+        assert!(m.from_mont(&m.add(&am, &bm)) == a.add_ref(&b) % p);
+        assert!(m.from_mont(&m.sub(&am, &bm)) == Number::mod_cal(&a.sub_ref(&b), p));
+        let h = m.half(&am);
+        assert!(m.add(&h, &h) == am);
+        // negative / unreduced input
+        assert!(m.to_mont(&(a.sub_ref(p))) == am);
+        assert!(m.to_mont(&(a.add_ref(p))) == am);
+    }
+}
+
+/// Jacobian operations (Montgomery domain) against affine operations
+#[test]
+fn montgomery_ec_test() {
+    println!("< Montgomery EC Test >");
+    for ecname in CURVES.iter() {
+        println!("> {}", ecname);
+        let eca: EcpA<BigInt> = EcpA::new(ecname);
+        let ecj: EcpJ<BigInt> = EcpJ::new(ecname);
+        let n = ecj.get_order();
+
+        let k1 = BigInt::gen_rand(&BigInt::from(1), &n);
+        let k2 = BigInt::gen_rand(&BigInt::from(1), &n);
+        let (p_a1, p_a2) = (eca.mul(&eca.get_gp(), &k1), eca.mul(&eca.get_gp(), &k2));
+        let (p_j1, p_j2) = (ecj.mul(&ecj.get_gp(), &k1), ecj.mul(&ecj.get_gp(), &k2));
+        assert!(ecj.equals(&p_j1, &p_a1));
+        assert!(ecj.equals(&p_j2, &p_a2));
+        assert!(ecj.equals(&ecj.mul_bin(&ecj.get_gp(), &k1), &p_a1));
+        assert!(ecj.equals(&ecj.mul_ct(&ecj.get_gp(), &k1), &p_a1));
+
+        assert!(ecj.equals(&ecj.add(&p_j1, &p_j2), &eca.add(&p_a1, &p_a2)));
+        assert!(ecj.equals(&ecj.add(&p_j1, &p_a2), &eca.add(&p_a1, &p_a2)));
+        assert!(ecj.equals(&ecj.double(&p_j1), &eca.double(&p_a1)));
+        assert!(ecj.equals(&ecj.add(&p_j1, &p_j1), &eca.double(&p_a1)));
+        assert!(ecj.is_zero(&ecj.add(&p_j1, &ecj.negate(&p_j1))));
+        assert!(ecj.is_zero(&ecj.double(&ecj.get_zero())));
+        assert!(ecj.equals(&ecj.add(&ecj.get_zero(), &p_j1), &p_j1));
+    }
+}
@@ -0,0 +1,142 @@
+//!
+//! Special-form Prime Reduction Test Program
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+extern crate ecc;
+
+use ibig::IBig;
+use num_bigint::BigInt;
+
+use ecc::curve_param::CurveParam;
+use ecc::ec_param::{EcParam, ParamOp};
+use ecc::ecc_j::EcpJ;
+use ecc::ed_param::EdParam;
+use ecc::field::PrimeField;
+use ecc::fixint::Fix521;
+use ecc::number::Number;
+use ecc::reduction::{Reduction, SpecialPrime};
+use ecc::EcOp;
+
+const SPECIAL: [&str; 10] = [
+    "secp160k1",
+    "secp160r1",
+    "secp160r2",
+    "secp192k1",
+    "secp192r1",
+    "secp224k1",
+    "secp224r1",
+    "secp256k1",
+    "secp384r1",
+    "secp521r1",
+];
+
+#[test]
+fn special_prime_test() {
+    println!("< Special-form Prime Reduction Test >");
+    for ecname in SPECIAL.iter() {
+        println!("> {}", ecname);
+        do_reduce_test::<BigInt>(ecname);
+        do_reduce_test::<IBig>(ecname);
+        do_reduce_test::<Fix521>(ecname);
+    }
+    for ecname in ["secp112r1", "secp128r1", "secp256r1", "brainpoolP256r1"] {
+        let p: BigInt = EcParam::new(ecname).get_prime();
+        assert!(SpecialPrime::try_new(&p).is_none());
+        assert!(matches!(
+            PrimeField::for_prime(&p).get_reduction(),
+            Reduction::Montgomery(_)
+        ));
+    }
+}
+
+/// The special-form curves get Reduction::Special from their prime alone
+#[test]
+fn special_select_test() {
+    println!("< Special-form Prime Selection Test >");
+    for ecname in SPECIAL.iter() {
+        let p: BigInt = EcParam::new(ecname).get_prime();
+        assert!(matches!(
+            PrimeField::for_prime(&p).get_reduction(),
+            Reduction::Special(_)
+        ));
+        let p: Fix521 = EcParam::new(ecname).get_prime();
+        assert!(matches!(Reduction::for_prime(&p), Reduction::Special(_)));
+    }
+    let p: BigInt = EdParam::new("edwards25519").get_prime();
+    assert!(matches!(Reduction::for_prime(&p), Reduction::Special(_)));
+    let p: BigInt = EdParam::new("edwards448").get_prime();
+    assert!(matches!(Reduction::for_prime(&p), Reduction::Montgomery(_)));
+
+    // a user-supplied curve is not matched by id
+    let ec = EcParam::new("secp256k1");
+    let param: CurveParam<BigInt> = CurveParam::new(
+        "custom",
+        ec.get_prime(),
+        ec.get_a(),
+        ec.get_b(),
+        (ec.get_gx(), ec.get_gy()),
+        ec.get_order(),
+        ec.get_cofactor(),
+    );
+    let ecp: EcpJ<BigInt> = EcpJ::from_param(&param);
+    let ecs: EcpJ<BigInt> = EcpJ::new("secp256k1");
+    let k = BigInt::from(12345u32);
+    assert!(ecp.equals(&ecp.mul(&ecp.get_gp(), &k), &ecs.mul(&ecs.get_gp(), &k)));
+}
+
+fn do_reduce_test<T: Number>(ecname: &str) {
+    let p: T = EcParam::new(ecname).get_prime();
+    let s = SpecialPrime::try_new(&p).unwrap();
+    let p1 = p.sub_ref(&T::one());
+
+    // edge values
+    for t in [
+        T::zero(),
+        T::one(),
+        p1.clone(),
+        p.clone(),
+        p.add_ref(&T::one()),
+        p.add_ref(&p),
+        p1.mul_ref(&p1),
+        p.mul_ref(&p1),
+    ] {
+        assert!(s.reduce(&t) == t.clone() % &p);
+    }
+    for _ in 0..50 {
+        let a = T::gen_rand(&T::zero(), &p);
+        let b = T::gen_rand(&T::zero(), &p);
+        let t = a.mul_ref(&b);
+        assert!(s.reduce(&t) == t % &p);
+    }
+}
+
+/// Special-form reduction (EcpJ::new) against Montgomery form (EcpJ::with_field)
+#[test]
+fn special_ec_test() {
+    println!("< Special-form Prime EC Test >");
+    for ecname in SPECIAL.iter() {
+        println!("> {}", ecname);
+        do_ec_test::<BigInt>(ecname);
+        do_ec_test::<Fix521>(ecname);
+    }
+}
+
+fn do_ec_test<T: Number>(ecname: &str) {
+    let ecs: EcpJ<T> = EcpJ::new(ecname);
+    let param = EcParam::new(ecname);
+    let ecm: EcpJ<T> = EcpJ::with_field(&param, PrimeField::new(&param.get_prime()));
+    let n = ecs.get_order();
+
+    let k1 = T::gen_rand(&T::one(), &n);
+    let k2 = T::gen_rand(&T::one(), &n);
+    let p_s1 = ecs.mul(&ecs.get_gp(), &k1);
+    let p_s2 = ecs.mul_ct(&ecs.get_gp(), &k2);
+    let p_m1 = ecm.mul(&ecm.get_gp(), &k1);
+    let p_m2 = ecm.mul_ct(&ecm.get_gp(), &k2);
+    assert!(ecs.equals(&p_s1, &p_m1));
+    assert!(ecs.equals(&p_s2, &p_m2));
+    assert!(ecs.equals(&ecs.add(&p_s1, &p_s2), &ecm.add(&p_m1, &p_m2)));
+    assert!(ecs.equals(&ecs.double(&p_s1), &ecm.double(&p_m1)));
+    assert!(ecs.is_zero(&ecs.mul(&ecs.get_gp(), &n)));
+}
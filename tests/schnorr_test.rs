@@ -0,0 +1,199 @@
+//!
+//! BIP-340 Schnorr Signature Test Program
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+extern crate ecc;
+
+use ibig::IBig;
+use num_bigint::BigInt;
+use num_traits::One;
+
+use ecc::ecc_a::EcpA;
+use ecc::ecc_j::EcpJ;
+use ecc::fixint::Fix256;
+use ecc::number::Number;
+use ecc::schnorr::{tagged_hash, Schnorr, SchnorrSignature};
+use ecc::{EcError, EcOp};
+
+/// Official BIP-340 test vectors (bip-0340/test-vectors.csv)
+const VECTORS: &str = include_str!("data/bip340_test_vectors.csv");
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn bip340_vectors_test() {
+    println!("< BIP-340 Test Vectors >");
+    let ecj: EcpJ<BigInt> = EcpJ::new("secp256k1");
+    do_vectors_test(&ecj);
+    let ecj: EcpJ<Fix256> = EcpJ::new("secp256k1");
+    do_vectors_test(&ecj);
+    let ecj: EcpJ<IBig> = EcpJ::new("secp256k1");
+    do_vectors_test(&ecj);
+    let eca: EcpA<BigInt> = EcpA::new("secp256k1");
+    do_vectors_test(&eca);
+}
+
+fn do_vectors_test<T: Number>(ecp: &impl EcOp<T>) {
+    let schnorr = Schnorr::new(ecp);
+    let len = schnorr.get_len();
+
+    for line in VECTORS.lines().skip(1) {
+        let v: Vec<&str> = line.splitn(8, ',').collect();
+        let (index, sk, pk, aux, msg, sig) = (v[0], v[1], v[2], v[3], v[4], v[5]);
+        let expected = v[6] == "TRUE";
+        println!("> {} {}", index, v[7]);
+
+        let px = T::from_bytes_be(&unhex(pk));
+        let (msg, sig) = (unhex(msg), unhex(sig));
+        if !sk.is_empty() {
+            let d = T::from_bytes_be(&unhex(sk));
+            assert!(
+                schnorr.public_key(&d).unwrap() == px,
+                "public key {}",
+                index
+            );
+            let s = schnorr.sign_with_aux(&d, &msg, &unhex(aux)).unwrap();
+            assert_eq!(s.to_bytes(len), sig, "signature {}", index);
+        }
+        let s = SchnorrSignature::from_bytes(&sig).unwrap();
+        assert_eq!(schnorr.verify(&px, &msg, &s), expected, "verify {}", index);
+    }
+}
+
+#[test]
+fn schnorr_test() {
+    println!("< BIP-340 Schnorr Test >");
+    let ecj: EcpJ<BigInt> = EcpJ::new("secp256k1");
+    let schnorr = Schnorr::new(&ecj);
+    let n = ecj.get_order();
+
+    for msg in [&b""[..], b"abc", &[0x5a; 100]] {
+        let (d, px) = schnorr.gen_keypair();
+        let sig = schnorr.sign(&d, msg).unwrap();
+        assert!(schnorr.verify(&px, msg, &sig));
+        // the same key as n - d (x-only)
+        assert_eq!(schnorr.public_key(&(&n - &d)).unwrap(), px);
+        let sig2 = schnorr.sign(&(&n - &d), msg).unwrap();
+        assert!(schnorr.verify(&px, msg, &sig2));
+
+        // random auxiliary data
+        assert_ne!(schnorr.sign(&d, msg).unwrap(), sig);
+
+        // tampered message / signature / key
+        let mut bad = msg.to_vec();
+        bad.push(0);
+        assert!(!schnorr.verify(&px, &bad, &sig));
+        let bad_s = SchnorrSignature::new(sig.r().clone(), (sig.s() + BigInt::one()) % &n);
+        assert!(!schnorr.verify(&px, msg, &bad_s));
+        let (_, px2) = schnorr.gen_keypair();
+        assert!(!schnorr.verify(&px2, msg, &sig));
+    }
+
+    // lift_x: even y
+    let (_, px) = schnorr.gen_keypair();
+    let p_p = schnorr.lift_x(&px).unwrap();
+    assert!(ecj.on_curve(&p_p) && !ecj.to_affine(&p_p).y.test_bit(0));
+    assert_eq!(
+        schnorr.lift_x(&ecj.get_prime()).err(),
+        Some(EcError::CoordinateOutOfRange)
+    );
+
+    // invalid private key
+    for d in [BigInt::from(0), n.clone()] {
+        assert_eq!(schnorr.public_key(&d), Err(EcError::InvalidScalar));
+        assert_eq!(
+            schnorr.sign_with_aux(&d, b"", &[0; 32]),
+            Err(EcError::InvalidScalar)
+        );
+    }
+    assert_eq!(
+        SchnorrSignature::<BigInt>::from_bytes(&[0; 63]),
+        Err(EcError::InvalidLength(63))
+    );
+    // halves longer than Fix256 holds (104 octets)
+    assert_eq!(
+        SchnorrSignature::<Fix256>::from_bytes(&[0; 210]),
+        Err(EcError::InvalidLength(210))
+    );
+    assert!(SchnorrSignature::<Fix256>::from_bytes(&[0; 208]).is_ok());
+}
+
+#[test]
+fn tagged_hash_test() {
+    // SHA256(SHA256("BIP0340/challenge") || SHA256("BIP0340/challenge"))
+    let h = tagged_hash("BIP0340/challenge", &[]);
+    assert_eq!(
+        h.to_vec(),
+        unhex("C216D352F5818B7B4BEACD4AE0A26FE888080823D2A598856661BCD54F1B3713")
+    );
+    // data slices are concatenated
+    assert_eq!(
+        tagged_hash("TapLeaf", &[b"ab", b"c"]),
+        tagged_hash("TapLeaf", &[b"abc"])
+    );
+}
+
+#[test]
+fn verify_batch_test() {
+    println!("< BIP-340 Batch Verification Test >");
+    let ecj: EcpJ<BigInt> = EcpJ::new("secp256k1");
+    do_verify_batch(&ecj);
+    let ecj: EcpJ<Fix256> = EcpJ::new("secp256k1");
+    do_verify_batch(&ecj);
+    let eca: EcpA<BigInt> = EcpA::new("secp256k1");
+    do_verify_batch(&eca);
+}
+
+fn do_verify_batch<T: Number>(ecp: &impl EcOp<T>) {
+    let schnorr = Schnorr::new(ecp);
+    let n = ecp.get_order();
+
+    // a few keys, many signatures
+    let keys: Vec<(T, T)> = (0..3).map(|_| schnorr.gen_keypair()).collect();
+    let msgs: Vec<Vec<u8>> = (0..16u32).map(|i| i.to_be_bytes().to_vec()).collect();
+    let mut items: Vec<(T, Vec<u8>, SchnorrSignature<T>)> = msgs
+        .iter()
+        .enumerate()
+        .map(|(i, msg)| {
+            let (d, px) = &keys[i % 3];
+            (px.clone(), msg.clone(), schnorr.sign(d, msg).unwrap())
+        })
+        .collect();
+    let batch = |items: &[(T, Vec<u8>, SchnorrSignature<T>)]| {
+        let refs: Vec<_> = items
+            .iter()
+            .map(|(px, msg, sig)| (px, &msg[..], sig))
+            .collect();
+        schnorr.verify_batch(&refs)
+    };
+    assert_eq!(batch(&items), Ok(()));
+    assert_eq!(batch(&[]), Ok(()));
+
+    // tampered message / s / key,  s out of range
+    items[1].1.push(0);
+    let sig = items[5].2.clone();
+    items[5].2 = SchnorrSignature::new(sig.r().clone(), (sig.s().add_ref(&T::one())) % &n);
+    items[6].0 = keys[(6 + 1) % 3].1.clone();
+    let sig = items[15].2.clone();
+    items[15].2 = SchnorrSignature::new(sig.r().clone(), n.clone());
+    assert_eq!(batch(&items), Err(vec![1, 5, 6, 15]));
+
+    // the official vectors in one batch
+    let mut items = Vec::new();
+    let mut expected = Vec::new();
+    for (i, line) in VECTORS.lines().skip(1).enumerate() {
+        let v: Vec<&str> = line.splitn(8, ',').collect();
+        let sig = SchnorrSignature::from_bytes(&unhex(v[5])).unwrap();
+        items.push((T::from_bytes_be(&unhex(v[2])), unhex(v[4]), sig));
+        if v[6] != "TRUE" {
+            expected.push(i);
+        }
+    }
+    assert_eq!(batch(&items), Err(expected));
+}
@@ -0,0 +1,134 @@
+//!
+//! Modular Square Root Test Program
+//!
+//  Written by blanclux
+//  This software is distributed on an "AS IS" basis WITHOUT WARRANTY OF ANY KIND.
+extern crate ecc;
+
+use ibig::IBig;
+use num_bigint::BigInt;
+use proptest::prelude::*;
+
+use ecc::ec_param::{EcParam, ParamOp};
+use ecc::ecc_a::EcpA;
+use ecc::ecc_e::EcpE;
+use ecc::ecc_j::EcpJ;
+use ecc::ed_param::EdParam;
+use ecc::fixint::Fix521;
+use ecc::number::Number;
+use ecc::{EcOp, WeierstrassOp};
+
+/// Primes of every shape besides the curve primes
+///   p = 5 mod 8: 13, 2^255 - 19,  p = 1 mod 8: 17, 41, 2^224 - 2^96 + 1 (secp224r1)
+const PRIMES: [&str; 4] = [
+    "d",
+    "11",
+    "29",
+    "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed",
+];
+
+fn primes<T: Number>() -> Vec<T> {
+    let mut v: Vec<T> = EcParam::curve_ids()
+        .iter()
+        .map(|id| EcParam::new(id).get_prime())
+        .collect();
+    v.extend(PRIMES.iter().map(|s| T::from_bytes_radix(s.as_bytes(), 16)));
+    v
+}
+
+fn check_sqrt<T: Number>(bytes: &[u8]) {
+    for p in primes::<T>() {
+        let a = Number::mod_cal(&T::from_bytes_be(bytes), &p);
+
+        // a^2 has the roots a and p - a
+        let a2 = a.mul_ref(&a) % &p;
+        let r = Number::mod_sqrt(&a2, &p).unwrap();
+        assert!(r == a || r == Number::mod_cal(&(T::zero() - &a), &p));
+
+        // a: root iff quadratic residue
+        match Number::mod_sqrt(&a, &p) {
+            Some(r) => {
+                assert!(a.jacobi(&p) >= 0);
+                assert!(r.mul_ref(&r) % &p == a);
+            }
+            None => assert!(a.jacobi(&p) == -1),
+        }
+    }
+}
+
+#[test]
+fn sqrt_edge_test() {
+    println!("< Modular Square Root Edge Test >");
+    for p in primes::<BigInt>() {
+        let zero = BigInt::from(0);
+        assert_eq!(Number::mod_sqrt(&zero, &p), Some(zero.clone()));
+        assert_eq!(Number::mod_sqrt(&p, &p), Some(zero));
+        assert_eq!(
+            Number::mod_sqrt(&BigInt::from(1), &p).map(|r| r.clone() * &r % &p),
+            Some(BigInt::from(1))
+        );
+        // smallest non-residue
+        let mut q = BigInt::from(2);
+        while q.jacobi(&p) != -1 {
+            q += 1;
+        }
+        assert_eq!(Number::mod_sqrt(&q, &p), None);
+    }
+}
+
+/// x with no point on the curve: point_from_x returns None
+#[test]
+fn point_from_x_test() {
+    println!("< point_from_x Test >");
+    for id in EcParam::curve_ids() {
+        let ecp: EcpJ<BigInt> = EcpJ::new(id);
+        let p = ecp.get_prime();
+        let mut x = BigInt::from(0);
+        loop {
+            let y2 = (&x * &x * &x + ecp.get_a() * &x + ecp.get_b()) % &p;
+            if y2.jacobi(&p) == -1 {
+                break;
+            }
+            x += 1;
+        }
+        assert!(ecp.point_from_x(&x, 0).is_none());
+        assert!(ecp
+            .try_decode_point_partial(
+                &[&[2u8][..], &x.to_bytes_be_pad(ecp.get_field_len())].concat()
+            )
+            .is_err());
+
+        // x out of range:  None (no panic)
+        assert!(ecp.point_from_x(&p, 0).is_none());
+        assert!(ecp.point_from_x(&(&p + 1), 1).is_none());
+        assert!(ecp.point_from_x(&BigInt::from(-1), 0).is_none());
+        let eca: EcpA<BigInt> = EcpA::new(id);
+        assert!(eca.point_from_x(&p, 0).is_none());
+    }
+    for id in EdParam::curve_ids() {
+        let ece: EcpE<BigInt> = EcpE::new(id);
+        let p = ece.get_prime();
+        assert!(ece.point_from_x(&p, 0).is_none());
+        assert!(ece.point_from_y(&p, 0).is_none());
+        assert!(ece.point_from_y(&(&p + 1), 1).is_none());
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn sqrt_prop_bigint(bytes in proptest::collection::vec(any::<u8>(), 1..67)) {
+        check_sqrt::<BigInt>(&bytes);
+    }
+
+    #[test]
+    fn sqrt_prop_ibig(bytes in proptest::collection::vec(any::<u8>(), 1..67)) {
+        check_sqrt::<IBig>(&bytes);
+    }
+
+    #[test]
+    fn sqrt_prop_fixint(bytes in proptest::collection::vec(any::<u8>(), 1..66)) {
+        check_sqrt::<Fix521>(&bytes);
+    }
+}